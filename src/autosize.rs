@@ -0,0 +1,133 @@
+// Fitting the column widths to their contents
+
+use super::{CellValue, SmartTable};
+use fltk::{
+    app, draw,
+    enums::*,
+    prelude::{TableExt, WidgetExt},
+    table,
+};
+
+// Rows of a model measured when autosizing, the data of the table is measured entirely
+const MODEL_SAMPLE_ROWS: i32 = 1000;
+
+// Space kept on the sides of the widest text
+const MARGIN: i32 = 8;
+
+impl SmartTable {
+    /// Fit the column's width to its header and the text of its cells, measured in their fonts.
+    /// Also done by double-clicking the divider at the right of the column's header.
+    /// A width mode set on the column is dropped
+    pub fn autosize_col(&mut self, col: i32) {
+        if !self.is_col_visible(col) {
+            return;
+        }
+        let opts = *self.opts.try_lock().unwrap();
+        let measure = |txt: &str, font: Font, size: i32| {
+            draw::set_font(font, size);
+            // Only the widest line counts
+            txt.lines()
+                .map(|l| draw::measure(l, false).0)
+                .max()
+                .unwrap_or(0)
+        };
+        let model = self.model.try_lock().unwrap();
+        let (header, widest) = if let Some(m) = model.as_ref() {
+            let widest = (0..m.row_count().min(MODEL_SAMPLE_ROWS))
+                .map(|r| measure(&m.cell(r, col), opts.cell_font, opts.cell_font_size))
+                .max();
+            (m.col_header(col), widest)
+        } else {
+            let widest = self
+                .data
+                .try_lock()
+                .unwrap()
+                .iter()
+                .map(|r| {
+                    let c = &r[col as usize];
+                    let size = c.font_size.unwrap_or(opts.cell_font_size);
+                    if c.label.is_empty() {
+                        0
+                    } else if let Some(CellValue::Bool(_)) = c.value {
+                        // Room for the checkbox
+                        size + 6
+                    } else {
+                        measure(&c.label, c.font.unwrap_or(opts.cell_font), size)
+                    }
+                })
+                .max();
+            (
+                self.col_headers.try_lock().unwrap()[col as usize].clone(),
+                widest,
+            )
+        };
+        drop(model);
+        let header = measure(&header, opts.header_font, opts.header_font_size) + MARGIN;
+        let cells = widest.unwrap_or(0) + 2 * (opts.cell_padding + opts.cell_border_width) + MARGIN;
+        let w = header.max(cells).max(self.table.col_resize_min());
+        self.col_props.try_lock().unwrap()[col as usize].width = None;
+        self.set_col_width(col, w);
+        self.table.redraw();
+    }
+
+    /// Fit the widths of all the visible columns, see `autosize_col()`
+    pub fn autosize_all_cols(&mut self) {
+        for col in 0..self.column_count() {
+            self.autosize_col(col);
+        }
+    }
+
+    /// Widen or narrow the row headers to fit their longest label
+    pub fn autosize_row_header(&mut self) {
+        let opts = *self.opts.try_lock().unwrap();
+        draw::set_font(
+            opts.row_header_font.unwrap_or(opts.header_font),
+            opts.row_header_font_size.unwrap_or(opts.header_font_size),
+        );
+        let measure = |txt: &str| draw::measure(txt, false).0;
+        let widest = match self.model.try_lock().unwrap().as_ref() {
+            Some(m) => (0..m.row_count().min(MODEL_SAMPLE_ROWS))
+                .map(|r| measure(&m.row_header(r)))
+                .max(),
+            None => self
+                .row_headers
+                .try_lock()
+                .unwrap()
+                .iter()
+                .map(|h| measure(h))
+                .max(),
+        };
+        let w = widest.unwrap_or(0) + MARGIN;
+        if opts.rtl {
+            // The row headers are the trailing column
+            let col = self.table.cols() - 1;
+            self.table.set_col_width(col, w);
+        }
+        self.table.set_row_header_width(w);
+        self.table.redraw();
+    }
+
+    // Autosizes a column when the divider at the right of its header is double-clicked
+    pub(crate) fn handle_autosize_click(
+        &mut self,
+        t: &table::TableRow,
+        ev: Event,
+        rtl: bool,
+    ) -> bool {
+        if ev != Event::Push || app::event_clicks_num() == 0 {
+            return false;
+        }
+        let col = match t.cursor2rowcol() {
+            Some((table::TableContext::ColHeader, _, col, table::TableResizeFlag::ColRight)) => col,
+            Some((table::TableContext::ColHeader, _, col, table::TableResizeFlag::ColLeft)) => {
+                col - 1
+            }
+            _ => return false,
+        };
+        let Some(col) = Self::data_col(rtl, t.cols() - rtl as i32, col) else {
+            return false;
+        };
+        self.autosize_col(col);
+        true
+    }
+}
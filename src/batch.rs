@@ -0,0 +1,99 @@
+// Editing many cells under a single lock of the data
+
+use super::{CellMatrix, CellValue, SmartTable, TableModel};
+use fltk::prelude::*;
+use std::sync::MutexGuard;
+
+/// Access to the cells during `SmartTable::update()`, the data stays locked until it ends.
+/// With a model set, the cells are those of the model
+pub struct TableBatch<'a> {
+    data: MutexGuard<'a, CellMatrix>,
+    model: MutexGuard<'a, Option<Box<dyn TableModel>>>,
+    // (row, col, old, new) of each change
+    edits: Vec<(i32, i32, String, String)>,
+}
+
+impl TableBatch<'_> {
+    /// Returns the row count
+    pub fn row_count(&self) -> i32 {
+        SmartTable::source_size(&self.data, self.model.as_deref()).0
+    }
+
+    /// Returns the column count
+    pub fn column_count(&self) -> i32 {
+        SmartTable::source_size(&self.data, self.model.as_deref()).1
+    }
+
+    /// Get the cell value, using the row and column to index the data
+    pub fn cell_value(&self, row: i32, col: i32) -> String {
+        if let Some(m) = self.model.as_ref() {
+            return m.cell(row, col);
+        }
+        self.data[row as usize][col as usize].label.clone()
+    }
+
+    /// Set the cell value, using the row and column to index the data
+    pub fn set_cell_value(&mut self, row: i32, col: i32, val: &str) {
+        if let Some(m) = self.model.as_mut() {
+            let old = m.cell(row, col);
+            m.set_cell(row, col, val);
+            self.edits.push((row, col, old, val.to_string()));
+            return;
+        }
+        let cell = &mut self.data[row as usize][col as usize];
+        let old = std::mem::take(&mut cell.label);
+        cell.set_text(val.to_string());
+        self.edits.push((row, col, old, val.to_string()));
+    }
+
+    /// Set a typed cell value, see `SmartTable::set_cell_typed()`.
+    /// A model is given the value's text
+    pub fn set_cell_typed<V: Into<CellValue>>(&mut self, row: i32, col: i32, val: V) {
+        if self.model.is_some() {
+            return self.set_cell_value(row, col, &val.into().to_string());
+        }
+        let cell = &mut self.data[row as usize][col as usize];
+        let old = cell.label.clone();
+        cell.set_value(val.into());
+        let new = cell.label.clone();
+        self.edits.push((row, col, old, new));
+    }
+}
+
+impl SmartTable {
+    /// Make many changes to the cells with the data locked once, e.g. to fill a large table.
+    /// The column watchers are notified and the table is redrawn once at the end,
+    /// and one `undo()` reverts all the changes
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut TableBatch) -> R) -> R {
+        let (data, model) = (self.data.clone(), self.model.clone());
+        let mut batch = TableBatch {
+            data: data.try_lock().unwrap(),
+            model: model.try_lock().unwrap(),
+            edits: vec![],
+        };
+        let ret = f(&mut batch);
+        let TableBatch {
+            data: guard,
+            model: model_guard,
+            edits,
+        } = batch;
+        drop((guard, model_guard));
+        self.record_step(edits);
+        ret
+    }
+
+    // Notifies the column watchers of the (row, col, old, new) changes made to the data,
+    // records them as one undo step and redraws the table once
+    pub(crate) fn record_step(&mut self, edits: Vec<(i32, i32, String, String)>) {
+        if edits.is_empty() {
+            return;
+        }
+        for (row, col, _, new) in &edits {
+            Self::notify_col_watchers(&self.col_watchers, *row, *col, new);
+        }
+        self.mark_changed();
+        let depth = self.opts.try_lock().unwrap().undo_depth;
+        self.undo_stack.try_lock().unwrap().push_step(edits, depth);
+        self.table.redraw();
+    }
+}
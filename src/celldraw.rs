@@ -0,0 +1,67 @@
+// Custom drawing of the cells, on top of or instead of the default one
+
+use super::{Cell, ColProps, SmartTable, TableOpts};
+use fltk::{enums::Color, prelude::WidgetExt};
+
+pub(crate) type CellDrawFn = Box<dyn Fn(&mut DrawCtx) + Send>;
+
+/// A cell being drawn by the function set with `SmartTable::set_cell_draw()`.
+/// Drawing is clipped to the cell's bounds
+pub struct DrawCtx<'a> {
+    /// The row in the data
+    pub row: i32,
+    /// The column in the data
+    pub col: i32,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    /// The text of the cell
+    pub value: &'a str,
+    /// Whether the cell is drawn selected
+    pub selected: bool,
+    pub(crate) cell: &'a Cell,
+    pub(crate) props: &'a ColProps,
+    pub(crate) tinted: bool,
+    pub(crate) band: Option<Color>,
+    pub(crate) opts: &'a TableOpts,
+}
+
+impl DrawCtx<'_> {
+    /// Draw the cell the way the table does without a custom drawing function
+    pub fn draw_default(&self) {
+        SmartTable::draw_data(
+            self.cell,
+            self.props,
+            self.x,
+            self.y,
+            self.w,
+            self.h,
+            self.selected,
+            self.tinted,
+            self.band,
+            self.opts,
+        );
+    }
+
+    /// Returns the options the table is drawn with, scaled and themed
+    pub fn opts(&self) -> &TableOpts {
+        self.opts
+    }
+}
+
+impl SmartTable {
+    /// Set a function drawing the cells, e.g. to add a progress bar or an icon to a few of them.
+    /// Call `ctx.draw_default()` for the cells drawn as usual, before drawing over them if needed.
+    /// The data is locked while it runs, use the context's fields instead of the table's getters
+    pub fn set_cell_draw<F: Fn(&mut DrawCtx) + Send + 'static>(&mut self, f: F) {
+        *self.cell_draw.try_lock().unwrap() = Some(Box::new(f));
+        self.table.redraw();
+    }
+
+    /// Remove the cell drawing function
+    pub fn clear_cell_draw(&mut self) {
+        *self.cell_draw.try_lock().unwrap() = None;
+        self.table.redraw();
+    }
+}
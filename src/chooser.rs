@@ -0,0 +1,62 @@
+// The column chooser, a popup menu toggling the visibility of the columns
+
+use super::{menu_label, SmartTable};
+use fltk::{
+    app,
+    enums::*,
+    menu::{self, MenuFlag},
+    prelude::{MenuExt, TableExt, WidgetBase, WidgetExt},
+    table,
+};
+
+impl SmartTable {
+    // Shows the column chooser on a right-click of the column headers
+    pub(crate) fn handle_col_chooser(&mut self, t: &table::TableRow) -> bool {
+        if app::event_mouse_button() != app::MouseButton::Right {
+            return false;
+        }
+        let Some((table::TableContext::ColHeader, ..)) = t.cursor2rowcol() else {
+            return false;
+        };
+        self.show_col_chooser();
+        true
+    }
+
+    /// Pop up a menu at the mouse listing the columns, with checkboxes showing and hiding them.
+    /// The last visible column can't be hidden
+    pub fn show_col_chooser(&mut self) {
+        let cols = self.column_count();
+        let headers: Vec<String> = match self.model.try_lock().unwrap().as_ref() {
+            Some(m) => (0..cols).map(|c| m.col_header(c)).collect(),
+            None => self.col_headers.try_lock().unwrap().clone(),
+        };
+        let visible: Vec<bool> = (0..cols).map(|c| self.is_col_visible(c)).collect();
+        let shown = visible.iter().filter(|v| **v).count();
+        let mut menu = menu::MenuButton::default();
+        menu.hide();
+        for (c, header) in headers.iter().enumerate() {
+            let label = if header.is_empty() {
+                self.col_label(c as i32)
+            } else {
+                menu_label(header)
+            };
+            let mut flag = MenuFlag::Toggle;
+            if visible[c] {
+                flag |= MenuFlag::Value;
+                if shown == 1 {
+                    flag |= MenuFlag::Inactive;
+                }
+            }
+            menu.add(&label, Shortcut::None, flag, |_| {});
+        }
+        if menu.popup().is_some() {
+            let col = menu.value();
+            if self.is_col_visible(col) {
+                self.hide_col(col);
+            } else {
+                self.show_col(col);
+            }
+        }
+        WidgetBase::delete(menu);
+    }
+}
@@ -0,0 +1,166 @@
+// Suggestions completing the text typed in the inline editor, compiled in with the `editing` feature
+
+use super::SmartTable;
+use fltk::{
+    app, browser,
+    enums::*,
+    input,
+    prelude::{BrowserExt, GroupExt, InputExt, WidgetExt},
+    window,
+};
+
+// Most suggestions shown at once
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Where the suggestions of a column's editor come from, see `SmartTable::set_col_completion()`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Completion {
+    /// No suggestions
+    #[default]
+    None,
+    /// The values of the list
+    List(Vec<String>),
+    /// The distinct values already in the column
+    ColValues,
+}
+
+impl SmartTable {
+    /// Set the suggestions popped up below the column's text editor while typing, matching the
+    /// start of the typed text. Up and Down pick one, Enter or a click commits it
+    pub fn set_col_completion(&mut self, col: i32, completion: Completion) {
+        self.col_props.try_lock().unwrap()[col as usize].completion = completion;
+    }
+
+    /// Get where the suggestions of the column's editor come from
+    pub fn col_completion(&self, col: i32) -> Completion {
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .get(col as usize)
+            .map(|p| p.completion.clone())
+            .unwrap_or_default()
+    }
+
+    // Creates the list of suggestions, committing the clicked one with the editor `inp`
+    pub(crate) fn setup_completion(&mut self, win: &mut window::Window, inp: &input::Input) {
+        let mut list = browser::HoldBrowser::default();
+        win.add(&list);
+        list.hide();
+        list.set_callback({
+            let mut inp = inp.clone();
+            move |l| {
+                let Some(text) = l.selected_text() else {
+                    return;
+                };
+                l.hide();
+                inp.set_value(&text);
+                inp.take_focus().ok();
+                inp.do_callback();
+            }
+        });
+        self.completions = Some(list);
+    }
+
+    // Updates and navigates the suggestions of the editor of column `col`,
+    // returns whether the event was used
+    pub(crate) fn handle_completion(
+        &mut self,
+        inp: &mut input::Input,
+        ev: Event,
+        col: i32,
+    ) -> bool {
+        let Some(mut list) = self.completions.clone() else {
+            return false;
+        };
+        match ev {
+            Event::KeyDown if list.visible() => match app::event_key() {
+                Key::Down => {
+                    list.select((list.value() + 1).min(list.size()));
+                    true
+                }
+                Key::Up => {
+                    list.select((list.value() - 1).max(1));
+                    true
+                }
+                Key::Enter | Key::KPEnter if list.value() > 0 => {
+                    if let Some(text) = list.selected_text() {
+                        inp.set_value(&text);
+                    }
+                    list.hide();
+                    inp.do_callback();
+                    true
+                }
+                _ => false,
+            },
+            Event::KeyUp if list.visible() && app::event_key() == Key::Escape => {
+                // Only the suggestions are closed, a second Escape cancels the edit
+                list.hide();
+                true
+            }
+            Event::KeyUp
+                if !matches!(
+                    app::event_key(),
+                    Key::Up | Key::Down | Key::Enter | Key::KPEnter | Key::Escape | Key::Tab
+                ) =>
+            {
+                self.suggest(inp, &mut list, col);
+                false
+            }
+            // Clicking a suggestion takes the focus without ending the edit
+            Event::Unfocus if app::focus().is_some_and(|f| f.is_same(&list)) => true,
+            Event::Unfocus | Event::Hide => {
+                list.hide();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    // Fills the list with the values of the column's completion starting with the typed text,
+    // and shows it below the editor, or above it near the bottom of the window
+    fn suggest(&self, inp: &input::Input, list: &mut browser::HoldBrowser, col: i32) {
+        let values = match self.col_completion(col) {
+            Completion::None => return,
+            Completion::List(values) => values,
+            Completion::ColValues => {
+                let mut values = self.col(col);
+                values.sort();
+                values.dedup();
+                values
+            }
+        };
+        let typed = inp.value().to_lowercase();
+        let matches: Vec<String> = values
+            .into_iter()
+            .filter(|v| {
+                !typed.is_empty() && v.to_lowercase().starts_with(&typed) && *v != inp.value()
+            })
+            .take(MAX_SUGGESTIONS)
+            .collect();
+        if matches.is_empty() {
+            list.hide();
+            return;
+        }
+        list.clear();
+        for m in &matches {
+            list.add(m);
+        }
+        let h = matches.len() as i32 * (list.text_size() + 4) + 4;
+        Self::place_suggestions(inp, list, h);
+        list.show();
+        list.redraw();
+    }
+
+    // Moves the list of suggestions of height `h` below the editor, or above it near the bottom
+    // of the window
+    pub(crate) fn place_suggestions(inp: &input::Input, list: &mut browser::HoldBrowser, h: i32) {
+        let below = inp.y() + inp.h();
+        let win_h = inp.window().map_or(i32::MAX, |w| w.h());
+        let y = if below + h > win_h {
+            inp.y() - h
+        } else {
+            below
+        };
+        list.resize(inp.x(), y, inp.w(), h);
+    }
+}
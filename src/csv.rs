@@ -0,0 +1,398 @@
+// Reading and writing the table data as delimited text
+
+use super::{SmartTable, StringMatrix, TableOpts};
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+
+// Code points of the windows-1252 bytes 0x80..=0x9F, the rest of the range matches latin-1.
+// Bytes the encoding leaves undefined map to the C1 control of the same value
+const WINDOWS_1252_C1: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+/// The text encoding of imported and exported files
+#[derive(Debug, Clone, Copy, Default)]
+pub enum TextEncoding {
+    /// UTF-8, a leading byte order mark is skipped. Invalid sequences are replaced with U+FFFD
+    #[default]
+    Utf8,
+    /// ISO-8859-1
+    Latin1,
+    /// Windows-1252, the usual encoding of files saved by older Windows software
+    Windows1252,
+    /// Any other encoding, given as decode and encode functions.
+    /// For example with the encoding_rs crate:
+    /// `TextEncoding::Custom { decode: |b| SHIFT_JIS.decode(b).0.into_owned(), encode: |s| SHIFT_JIS.encode(s).0.into_owned() }`
+    Custom {
+        decode: fn(&[u8]) -> String,
+        encode: fn(&str) -> Vec<u8>,
+    },
+}
+
+impl TextEncoding {
+    /// Decode the bytes into a string
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            TextEncoding::Utf8 => {
+                let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+                String::from_utf8_lossy(bytes).into_owned()
+            }
+            TextEncoding::Latin1 => bytes.iter().map(|b| *b as char).collect(),
+            TextEncoding::Windows1252 => bytes
+                .iter()
+                .map(|b| match b {
+                    0x80..=0x9F => WINDOWS_1252_C1[(b - 0x80) as usize],
+                    _ => *b as char,
+                })
+                .collect(),
+            TextEncoding::Custom { decode, .. } => decode(bytes),
+        }
+    }
+
+    /// Encode the string, characters the encoding can't represent become `?`
+    pub fn encode(&self, txt: &str) -> Vec<u8> {
+        match self {
+            TextEncoding::Utf8 => txt.as_bytes().to_vec(),
+            TextEncoding::Latin1 => txt
+                .chars()
+                .map(|c| u8::try_from(c).unwrap_or(b'?'))
+                .collect(),
+            TextEncoding::Windows1252 => txt
+                .chars()
+                .map(|c| {
+                    if let Some(i) = WINDOWS_1252_C1.iter().position(|w| *w == c) {
+                        0x80 + i as u8
+                    } else {
+                        match u8::try_from(c) {
+                            Ok(b) if !(0x80..=0x9F).contains(&b) => b,
+                            _ => b'?',
+                        }
+                    }
+                })
+                .collect(),
+            TextEncoding::Custom { encode, .. } => encode(txt),
+        }
+    }
+}
+
+/// Which fields are quoted when writing CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvQuoting {
+    /// Only fields holding delimiters, quotes or line breaks
+    #[default]
+    Minimal,
+    /// Every field
+    All,
+    /// Every field which isn't a number
+    NonNumeric,
+}
+
+/// Options for reading and writing CSV
+#[derive(Debug, Clone, Copy)]
+pub struct CsvOpts {
+    pub delimiter: char,
+    pub quote: char,
+    pub encoding: TextEncoding,
+    /// The first record holds the column headers
+    pub has_header: bool,
+    /// The first field of every record holds the row header
+    pub has_row_headers: bool,
+    /// Which fields are quoted when writing
+    pub quoting: CsvQuoting,
+}
+
+impl Default for CsvOpts {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote: '"',
+            encoding: TextEncoding::Utf8,
+            has_header: false,
+            has_row_headers: false,
+            quoting: CsvQuoting::Minimal,
+        }
+    }
+}
+
+// Splits the text into records of fields. Quoted fields can hold delimiters, line breaks
+// and doubled quotes
+pub(crate) fn parse(txt: &str, opts: &CsvOpts) -> StringMatrix {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = txt.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c == opts.quote {
+                if chars.peek() == Some(&opts.quote) {
+                    field.push(c);
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == opts.quote && field.is_empty() {
+            quoted = true;
+        } else if c == opts.delimiter {
+            row.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            row.push(std::mem::take(&mut field));
+            rows.push(std::mem::take(&mut row));
+        } else {
+            field.push(c);
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+// Quotes the field if it holds the delimiter, the quote or a line break
+pub(crate) fn quote_field(field: &str, opts: &CsvOpts) -> String {
+    let quoted = match opts.quoting {
+        CsvQuoting::Minimal => field.contains([opts.delimiter, opts.quote, '\n', '\r']),
+        CsvQuoting::All => true,
+        CsvQuoting::NonNumeric => {
+            field.parse::<f64>().is_err() || field.contains([opts.delimiter, opts.quote])
+        }
+    };
+    if quoted {
+        let q = opts.quote.to_string();
+        format!("{q}{}{q}", field.replace(&q, &q.repeat(2)))
+    } else {
+        field.to_string()
+    }
+}
+
+impl SmartTable {
+    /// Construct a table holding the records read from CSV, size it with the builder methods.
+    /// It uses the default TableOpts, restyle it with the setters such as
+    /// `set_default_cell_font()` and `set_header_color()`
+    pub fn from_csv<R: Read>(reader: R, opts: &CsvOpts) -> io::Result<Self> {
+        let mut table = Self::default().with_opts(TableOpts {
+            rows: 0,
+            cols: 0,
+            ..Default::default()
+        });
+        table.load_csv(reader, opts)?;
+        Ok(table)
+    }
+
+    /// Construct a table holding the records of a CSV file, see `from_csv()`
+    pub fn from_csv_file<P: AsRef<Path>>(path: P, opts: &CsvOpts) -> io::Result<Self> {
+        Self::from_csv(BufReader::new(File::open(path)?), opts)
+    }
+
+    /// Replace the data with the records of a CSV file, resizing the table to fit
+    pub fn load_csv_file<P: AsRef<Path>>(&mut self, path: P, opts: &CsvOpts) -> io::Result<()> {
+        self.load_csv(BufReader::new(File::open(path)?), opts)
+    }
+
+    /// Replace the data with the records read from CSV, resizing the table to fit
+    pub fn load_csv<R: Read>(&mut self, mut reader: R, opts: &CsvOpts) -> io::Result<()> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        let mut records = parse(&opts.encoding.decode(&bytes), opts);
+        let row_headers: Vec<String> = if opts.has_row_headers {
+            records
+                .iter_mut()
+                .map(|r| {
+                    if r.is_empty() {
+                        String::new()
+                    } else {
+                        r.remove(0)
+                    }
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        self.load_records(records, opts.has_header);
+        // The header record's first field is the corner above the row headers
+        let skip = opts.has_header as usize;
+        let mut headers = self.row_headers.try_lock().unwrap();
+        for (h, v) in headers.iter_mut().zip(row_headers.into_iter().skip(skip)) {
+            *h = v;
+        }
+        Ok(())
+    }
+
+    // Replaces the data with the records, taking the column headers from the first one if asked
+    pub(crate) fn load_records(&mut self, mut records: StringMatrix, has_header: bool) {
+        let header = if has_header && !records.is_empty() {
+            Some(records.remove(0))
+        } else {
+            None
+        };
+        self.replace_data_frozen(records);
+        if let Some(header) = header {
+            let mut col_headers = self.col_headers.try_lock().unwrap();
+            for (h, v) in col_headers.iter_mut().zip(header) {
+                *h = v;
+            }
+        }
+    }
+
+    /// Write the data as CSV, preceded by the column headers if `has_header` is set
+    /// and with each row led by its header if `has_row_headers` is set
+    pub fn to_csv<W: Write>(&self, mut writer: W, opts: &CsvOpts) -> io::Result<()> {
+        let data = self.data.try_lock().unwrap();
+        let model = self.model.try_lock().unwrap();
+        let model = model.as_deref();
+        let row_headers = self.row_headers.try_lock().unwrap();
+        let delim = opts.delimiter.to_string();
+        let (nrows, ncols) = Self::source_size(&data, model);
+        let header = opts.has_header.then(|| match model {
+            Some(m) => (0..ncols).map(|c| m.col_header(c)).collect(),
+            None => self.col_headers.try_lock().unwrap().clone(),
+        });
+        let rows = (0..nrows).map(|r| {
+            let row_header = match model {
+                Some(m) => m.row_header(r),
+                None => row_headers.get(r as usize).cloned().unwrap_or_default(),
+            };
+            let fields = (0..ncols)
+                .filter_map(|c| Self::source_cell(&data, model, r, c))
+                .map(|c| c.label.clone())
+                .collect();
+            (row_header, fields)
+        });
+        for (row_header, fields) in header.map(|h| (String::new(), h)).into_iter().chain(rows) {
+            let fields: Vec<String> = opts
+                .has_row_headers
+                .then_some(row_header.as_str())
+                .into_iter()
+                .chain(fields.iter().map(|f| f.as_str()))
+                .map(|f| quote_field(f, opts))
+                .collect();
+            let mut line = fields.join(&delim);
+            line.push_str("\r\n");
+            writer.write_all(&opts.encoding.encode(&line))?;
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(v: &[&[&str]]) -> StringMatrix {
+        v.iter()
+            .map(|r| r.iter().map(|f| f.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parse_records() {
+        let opts = CsvOpts::default();
+        assert_eq!(
+            parse("a,b\n1,2\n", &opts),
+            rows(&[&["a", "b"], &["1", "2"]])
+        );
+        assert_eq!(parse("a,\r\n,b", &opts), rows(&[&["a", ""], &["", "b"]]));
+        assert!(parse("", &opts).is_empty());
+    }
+
+    #[test]
+    fn parse_quoted_fields() {
+        let opts = CsvOpts::default();
+        let txt = "\"x, y\",\"say \"\"hi\"\"\"\r\nz,\"multi\nline\"";
+        assert_eq!(
+            parse(txt, &opts),
+            rows(&[&["x, y", "say \"hi\""], &["z", "multi\nline"]])
+        );
+    }
+
+    #[test]
+    fn parse_other_delimiter_and_quote() {
+        let opts = CsvOpts {
+            delimiter: ';',
+            quote: '\'',
+            ..Default::default()
+        };
+        assert_eq!(parse("a;'b;c'", &opts), rows(&[&["a", "b;c"]]));
+    }
+
+    #[test]
+    fn quote_minimal() {
+        let opts = CsvOpts::default();
+        assert_eq!(quote_field("plain", &opts), "plain");
+        assert_eq!(quote_field("a,b", &opts), "\"a,b\"");
+        assert_eq!(quote_field("say \"hi\"", &opts), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote_field("l1\nl2", &opts), "\"l1\nl2\"");
+    }
+
+    #[test]
+    fn quote_all_and_non_numeric() {
+        let all = CsvOpts {
+            quoting: CsvQuoting::All,
+            ..Default::default()
+        };
+        assert_eq!(quote_field("1", &all), "\"1\"");
+        let non_numeric = CsvOpts {
+            quoting: CsvQuoting::NonNumeric,
+            ..Default::default()
+        };
+        assert_eq!(quote_field("1.5", &non_numeric), "1.5");
+        assert_eq!(quote_field("abc", &non_numeric), "\"abc\"");
+    }
+
+    #[test]
+    fn quoted_fields_parse_back() {
+        let opts = CsvOpts::default();
+        let fields = ["a,b", "say \"hi\"", "l1\r\nl2", ""];
+        let line: Vec<String> = fields.iter().map(|f| quote_field(f, &opts)).collect();
+        assert_eq!(parse(&line.join(","), &opts), rows(&[&fields]));
+    }
+
+    #[test]
+    fn utf8() {
+        let enc = TextEncoding::Utf8;
+        assert_eq!(enc.decode(b"\xEF\xBB\xBFh\xC3\xA9"), "h\u{E9}");
+        assert_eq!(enc.decode(b"a\xFF"), "a\u{FFFD}");
+        assert_eq!(enc.encode("h\u{E9}"), b"h\xC3\xA9");
+    }
+
+    #[test]
+    fn latin1() {
+        let enc = TextEncoding::Latin1;
+        assert_eq!(enc.decode(&[b'a', 0x80, 0xE9]), "a\u{80}\u{E9}");
+        assert_eq!(enc.encode("\u{E9}\u{20AC}"), [0xE9, b'?']);
+    }
+
+    #[test]
+    fn windows_1252() {
+        let enc = TextEncoding::Windows1252;
+        assert_eq!(enc.decode(&[0x80, 0x93, 0xE9]), "\u{20AC}\u{201C}\u{E9}");
+        assert_eq!(enc.encode("\u{20AC}\u{E9}\u{2713}"), [0x80, 0xE9, b'?']);
+        // C1 controls the encoding maps to other characters have no byte
+        assert_eq!(enc.encode("\u{85}"), [b'?']);
+        assert_eq!(
+            enc.decode(&enc.encode("\u{2018}x\u{2019}")),
+            "\u{2018}x\u{2019}"
+        );
+    }
+
+    #[test]
+    fn custom_encoding() {
+        let enc = TextEncoding::Custom {
+            decode: |b| String::from_utf8_lossy(b).to_uppercase(),
+            encode: |s| s.to_lowercase().into_bytes(),
+        };
+        assert_eq!(enc.decode(b"abc"), "ABC");
+        assert_eq!(enc.encode("ABC"), b"abc");
+    }
+}
@@ -0,0 +1,161 @@
+// Filter dropdowns, an arrow in the column headers popping up a menu of the column's values
+// to include or exclude
+
+use super::{menu_label, SmartTable, TableOpts};
+use fltk::{
+    app, draw,
+    enums::*,
+    menu::{self, MenuFlag},
+    prelude::{MenuExt, TableExt, WidgetBase, WidgetExt},
+    table,
+};
+use std::collections::{BTreeSet, HashSet};
+
+// Values listed at most, the menu gets too long to use past that
+const MAX_VALUES: usize = 500;
+
+impl SmartTable {
+    /// Only display the rows whose cell in the column isn't one of the values, as if they were
+    /// unchecked in the column's filter dropdown. No values display them all
+    pub fn set_col_excluded_values(&mut self, col: i32, values: &[&str]) {
+        self.edit_filter(|f| {
+            if values.is_empty() {
+                f.col_excluded.remove(&col);
+            } else {
+                let values = values.iter().map(|v| v.to_string()).collect();
+                f.col_excluded.insert(col, values);
+            }
+        });
+        self.refilter();
+    }
+
+    /// Returns the values excluded from the column, sorted
+    pub fn col_excluded_values(&self, col: i32) -> Vec<String> {
+        let mut values: Vec<String> = self
+            .filter
+            .try_lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|f| f.col_excluded.get(&col))
+            .map(|values| values.iter().cloned().collect())
+            .unwrap_or_default();
+        values.sort();
+        values
+    }
+
+    /// Pop up the column's filter dropdown at the mouse, listing "(All)" and the column's
+    /// distinct values with checkboxes. Picking a value includes or excludes its rows
+    pub fn show_filter_dropdown(&mut self, col: i32) {
+        let values: BTreeSet<String> =
+            self.with_col_cells(col, |cells| cells.map(|c| c.label.clone()).collect());
+        let values: Vec<String> = values.into_iter().take(MAX_VALUES).collect();
+        let mut excluded: HashSet<String> = self.col_excluded_values(col).into_iter().collect();
+        let mut menu = menu::MenuButton::default();
+        menu.hide();
+        let mut flag = MenuFlag::Toggle | MenuFlag::MenuDivider;
+        if excluded.is_empty() {
+            flag |= MenuFlag::Value;
+        }
+        menu.add("(All)", Shortcut::None, flag, |_| {});
+        for v in &values {
+            let label = if v.is_empty() {
+                "(Empty)".to_string()
+            } else {
+                menu_label(v)
+            };
+            let mut flag = MenuFlag::Toggle;
+            if !excluded.contains(v) {
+                flag |= MenuFlag::Value;
+            }
+            menu.add(&label, Shortcut::None, flag, |_| {});
+        }
+        if menu.popup().is_some() {
+            match menu.value() {
+                // Unchecking "(All)" excludes all the values, checking it includes them
+                0 if excluded.is_empty() => excluded = values.into_iter().collect(),
+                0 => excluded.clear(),
+                i => {
+                    let v = &values[i as usize - 1];
+                    if !excluded.remove(v) {
+                        excluded.insert(v.clone());
+                    }
+                }
+            }
+            let excluded: Vec<&str> = excluded.iter().map(|v| v.as_str()).collect();
+            self.set_col_excluded_values(col, &excluded);
+            let cb = self.on_filter_changed.clone();
+            if let Some(cb) = &mut *cb.try_lock().unwrap() {
+                cb(self);
+            };
+        }
+        WidgetBase::delete(menu);
+    }
+
+    // Width of the dropdown arrow's strip at the right of the column headers
+    pub(crate) fn dropdown_width(opts: &TableOpts) -> i32 {
+        opts.header_font_size + 4
+    }
+
+    // Draws the dropdown arrow at the right of the header, pressed in while values are excluded
+    pub(crate) fn draw_dropdown_arrow(
+        filtered: bool,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        opts: &TableOpts,
+    ) {
+        let dw = Self::dropdown_width(opts);
+        if filtered {
+            draw::draw_box(
+                FrameType::DownBox,
+                x + w - dw,
+                y + 2,
+                dw - 2,
+                h - 4,
+                opts.header_color,
+            );
+        }
+        let s = (opts.header_font_size / 3).max(3);
+        let (cx, cy) = (x + w - dw / 2 - 1, y + h / 2);
+        draw::set_draw_color(opts.header_font_color);
+        draw::draw_polygon(cx - s, cy - s / 2, cx + s, cy - s / 2, cx, cy + s / 2);
+    }
+
+    // Pops up the filter dropdown when the arrow of a column header is clicked
+    pub(crate) fn handle_filter_dropdown(
+        &mut self,
+        t: &table::TableRow,
+        ev: Event,
+        rtl: bool,
+    ) -> bool {
+        if ev != Event::Push || app::event_mouse_button() != app::MouseButton::Left {
+            return false;
+        }
+        let Some((table::TableContext::ColHeader, _, view_col, table::TableResizeFlag::None)) =
+            t.cursor2rowcol()
+        else {
+            return false;
+        };
+        let Some(col) = Self::data_col(rtl, t.cols() - rtl as i32, view_col) else {
+            return false;
+        };
+        let Some((x, y, w, h)) = t.find_cell(table::TableContext::ColHeader, 0, view_col) else {
+            return false;
+        };
+        // The filter row takes the bottom of the headers
+        let band = self
+            .filter_row
+            .try_lock()
+            .unwrap()
+            .as_ref()
+            .map_or(0, |row| row.band);
+        let opts = *self.opts.try_lock().unwrap();
+        let (ex, ey) = app::event_coords();
+        if ex < x + w - Self::dropdown_width(&opts) || ey >= y + h - band {
+            return false;
+        }
+        self.show_filter_dropdown(col);
+        true
+    }
+}
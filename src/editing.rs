@@ -0,0 +1,611 @@
+// The inline cell editors, compiled in with the `editing` feature
+
+use super::{mask, menu_label, CellData, CellValue, FocusLossPolicy, SmartTable};
+use fltk::{
+    app,
+    enums::*,
+    input, menu,
+    prelude::{DisplayExt, GroupExt, InputExt, MenuExt, TableExt, WidgetBase, WidgetExt},
+    table, text, window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The editor of a column's cells, see `SmartTable::set_col_editor()`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CellEditor {
+    /// Free text
+    #[default]
+    Text,
+    /// A dropdown picking one of the values
+    Choice(Vec<String>),
+    /// Booleans, a click toggles the cell between true and false
+    Check,
+    /// Text input restricted to integers
+    Int,
+    /// Text input restricted to floating point numbers
+    Float,
+    /// A text editor for text spanning several lines, Ctrl+Enter commits it
+    Multiline,
+    /// Text input restricted to a pattern, e.g. "9999-99-99", where 9 stands for a digit,
+    /// A for a letter, `*` for a letter or digit, and other characters for themselves.
+    /// Keystrokes not fitting it are rejected, and an incomplete value isn't committed
+    Mask(String),
+}
+
+impl SmartTable {
+    // Creates the widgets used to edit cells, committing into the cell captured by `cell`
+    pub(crate) fn setup_editor(&mut self, cell: &Rc<RefCell<CellData>>) {
+        self.inp = Some(input::Input::default());
+        self.choice = Some(menu::Choice::default());
+        self.text_editor = Some(text::TextEditor::default());
+        let mut inp = self.inp.as_ref().unwrap().clone();
+        let mut choice = self.choice.as_ref().unwrap().clone();
+        let mut text_editor = self.text_editor.as_ref().unwrap().clone();
+        inp.set_trigger(CallbackTrigger::EnterKey);
+        text_editor.set_buffer(text::TextBuffer::default());
+        // Called on Ctrl+Enter only
+        text_editor.set_trigger(CallbackTrigger::Never);
+        let win =
+            window::Window::from_dyn_widget_ptr(self.table.top_window().unwrap().as_widget_ptr());
+        let mut win = win.unwrap();
+        win.add(&inp);
+        win.add(&choice);
+        win.add(&text_editor);
+        inp.hide();
+        choice.hide();
+        text_editor.hide();
+        self.setup_completion(&mut win, &inp);
+
+        inp.set_callback({
+            let cell = cell.clone();
+            let mut this = self.clone();
+            move |i| {
+                let (row, col) = {
+                    let cell = cell.borrow();
+                    (cell.row, cell.col)
+                };
+                if this.commit_value(row, col, i.value(), i) {
+                    i.set_value("");
+                    i.hide();
+                    this.table.take_focus().ok();
+                }
+            }
+        });
+
+        choice.set_callback({
+            let cell = cell.clone();
+            let mut this = self.clone();
+            move |c| {
+                let (row, col) = {
+                    let cell = cell.borrow();
+                    (cell.row, cell.col)
+                };
+                let Some(val) = this.choice_items(col).get(c.value() as usize).cloned() else {
+                    return;
+                };
+                if this.commit_value(row, col, val, c) {
+                    c.hide();
+                    this.table.take_focus().ok();
+                }
+            }
+        });
+
+        text_editor.set_callback({
+            let cell = cell.clone();
+            let mut this = self.clone();
+            move |e| {
+                let (row, col) = {
+                    let cell = cell.borrow();
+                    (cell.row, cell.col)
+                };
+                let val = e.buffer().map_or(String::new(), |b| b.text());
+                if this.commit_value(row, col, val, e) {
+                    e.hide();
+                    this.table.take_focus().ok();
+                }
+            }
+        });
+
+        // Escape cancels the edit, Tab and Shift+Tab commit it and edit the next or previous cell.
+        // Moving the focus to another widget commits or cancels it, following the options
+        let editor_keys = {
+            let edit_error = self.edit_error.clone();
+            let cell = cell.clone();
+            let mut this = self.clone();
+            move |w: &mut dyn WidgetExt, ev| match ev {
+                Event::KeyUp if app::event_key() == Key::Escape => {
+                    if edit_error.try_lock().unwrap().take().is_some() {
+                        w.set_tooltip("");
+                    }
+                    w.hide();
+                    this.table.take_focus().ok();
+                    true
+                }
+                Event::Unfocus if w.visible() => {
+                    // The focus is already on the next widget, None when the window was left
+                    let Some(mut next) = app::focus() else {
+                        return false;
+                    };
+                    // Committing locks the options, the guard is dropped first
+                    let policy = this.opts.try_lock().unwrap().edit_focus_loss;
+                    match policy {
+                        FocusLossPolicy::Commit => w.do_callback(),
+                        FocusLossPolicy::Cancel => {
+                            if edit_error.try_lock().unwrap().take().is_some() {
+                                w.set_tooltip("");
+                            }
+                            w.hide();
+                        }
+                    }
+                    // Committing gives the focus back to the table
+                    next.take_focus().ok();
+                    false
+                }
+                Event::KeyDown if app::event_key() == Key::Tab => {
+                    w.do_callback();
+                    if !w.visible() {
+                        this.edit_next(&cell, !app::is_event_shift());
+                    }
+                    true
+                }
+                _ => false,
+            }
+        };
+        inp.handle({
+            let mut keys = editor_keys.clone();
+            let cell = cell.clone();
+            let mut this = self.clone();
+            move |i, ev| {
+                let col = cell.borrow().col;
+                this.handle_mask(i, ev, col) || this.handle_completion(i, ev, col) || keys(i, ev)
+            }
+        });
+        choice.handle({
+            let mut keys = editor_keys.clone();
+            move |c, ev| keys(c, ev)
+        });
+        text_editor.handle({
+            let mut keys = editor_keys;
+            move |e, ev| match ev {
+                Event::KeyDown
+                    if app::is_event_ctrl()
+                        && matches!(app::event_key(), Key::Enter | Key::KPEnter) =>
+                {
+                    e.do_callback();
+                    true
+                }
+                _ => keys(e, ev),
+            }
+        });
+    }
+
+    /// Set the editor of the column's cells
+    pub fn set_col_editor(&mut self, col: i32, editor: CellEditor) {
+        self.col_props.try_lock().unwrap()[col as usize].editor = editor;
+    }
+
+    /// Get the editor of the column's cells
+    pub fn col_editor(&self, col: i32) -> CellEditor {
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .get(col as usize)
+            .map(|p| p.editor.clone())
+            .unwrap_or_default()
+    }
+
+    // The typed value of a data cell, None with a model or out of bounds
+    fn cell_typed_at(&self, row: i32, col: i32) -> Option<CellValue> {
+        self.data
+            .try_lock()
+            .unwrap()
+            .get(row as usize)
+            .and_then(|r| r.get(col as usize))
+            .map(|c| c.value())
+    }
+
+    // Whether a click toggles the cell instead of opening an editor
+    fn toggles(&self, row: i32, col: i32) -> bool {
+        self.col_editor(col) == CellEditor::Check
+            || matches!(self.cell_typed_at(row, col), Some(CellValue::Bool(_)))
+    }
+
+    // Text of the cell to put in its editor, None when it's missing, locked or read-only
+    pub(crate) fn edit_text(&self, row: i32, col: i32) -> Option<String> {
+        if self.cell_read_only(row, col) {
+            return None;
+        }
+        if let Some(m) = self.model.try_lock().unwrap().as_ref() {
+            return Some(m.cell(row, col));
+        }
+        self.data
+            .try_lock()
+            .unwrap()
+            .get(row as usize)
+            .and_then(|r| r.get(col as usize))
+            .filter(|c| !c.locked)
+            .map(|c| c.label.clone())
+    }
+
+    // Opens the editor of the focused cell, returns whether it was opened
+    pub(crate) fn edit_focused(&mut self, cell: &RefCell<CellData>) -> bool {
+        let Some((view_row, view_col)) = Self::focus_of(&self.table) else {
+            return false;
+        };
+        let opts = *self.opts.try_lock().unwrap();
+        let Some(col) = Self::data_col(opts.rtl, self.column_count(), view_col) else {
+            return false;
+        };
+        let row = self.view_to_data_row(view_row);
+        let Some((x, y, w, h)) =
+            self.table
+                .find_cell(table::TableContext::Cell, view_row, view_col)
+        else {
+            return false;
+        };
+        cell.borrow_mut().select(row, col, x, y, w, h);
+        let scale = if opts.dpi_aware {
+            Self::dpi_factor(&self.table)
+        } else {
+            1.0
+        };
+        let min_h = (opts.cell_font_size as f32 * 1.5 * scale) as i32;
+        let text = self.edit_text(row, col);
+        self.open_editor(text, &cell.borrow(), min_h)
+    }
+
+    // Opens the editor of the focused cell holding the typed text instead of the cell's,
+    // for text and number editors accepting it. Returns whether it was opened
+    pub(crate) fn type_to_edit(&mut self, cell: &RefCell<CellData>, text: &str) -> bool {
+        let Some((view_row, view_col)) = Self::focus_of(&self.table) else {
+            return false;
+        };
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let Some(col) = Self::data_col(rtl, self.column_count(), view_col) else {
+            return false;
+        };
+        if self.toggles(self.view_to_data_row(view_row), col) {
+            return false;
+        }
+        let accepted = match self.col_editor(col) {
+            CellEditor::Text | CellEditor::Multiline => true,
+            CellEditor::Int => text.chars().all(|c| c.is_ascii_digit() || "+-".contains(c)),
+            CellEditor::Float => text
+                .chars()
+                .all(|c| c.is_ascii_digit() || "+-.eE".contains(c)),
+            CellEditor::Mask(m) => mask::mask_fits(&m, text),
+            CellEditor::Choice(_) | CellEditor::Check => false,
+        };
+        if !accepted || !self.edit_focused(cell) {
+            return false;
+        }
+        if let Some(inp) = self.inp.as_mut().filter(|i| i.visible()) {
+            inp.set_value(text);
+            inp.set_position(text.len() as i32).ok();
+        }
+        if let Some(editor) = self.text_editor.as_mut().filter(|e| e.visible()) {
+            if let Some(mut buf) = editor.buffer() {
+                buf.set_text(text);
+            }
+            editor.set_insert_position(text.len() as i32);
+        }
+        true
+    }
+
+    // Moves the focus to the next or previous cell, wrapping across rows, and opens its editor.
+    // Locked cells are skipped and checkboxes get the focus without being toggled.
+    // Returns whether such a cell was found
+    pub(crate) fn edit_next(&mut self, cell: &RefCell<CellData>, forward: bool) -> bool {
+        let Some((view_row, view_col)) = Self::focus_of(&self.table) else {
+            return false;
+        };
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let (rows, cols) = (self.row_count() as i64, self.column_count() as i64);
+        let Some(col) = Self::data_col(rtl, cols as i32, view_col) else {
+            return false;
+        };
+        let count = rows * cols;
+        let mut idx = view_row as i64 * cols + col as i64;
+        for _ in 1..count {
+            idx = (idx + if forward { 1 } else { -1 }).rem_euclid(count);
+            let (r, c) = ((idx / cols) as i32, (idx % cols) as i32);
+            if !self.is_col_visible(c) {
+                continue;
+            }
+            let vc = Self::display_col(rtl, cols as i32, c);
+            self.table.set_selection(r, vc, r, vc);
+            self.ensure_visible(r, c);
+            self.table.redraw();
+            if self.toggles(self.view_to_data_row(r), c) || self.edit_focused(cell) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn choice_items(&self, col: i32) -> Vec<String> {
+        match self.col_editor(col) {
+            CellEditor::Choice(items) => items,
+            _ => vec![],
+        }
+    }
+
+    // Stores an edited value after validating it, then calls the callbacks. Returns whether
+    // it was stored, otherwise the edit stays open: either the cell is locked or the
+    // validator refused the value, whose message is shown as the editor's tooltip
+    fn commit_value(
+        &mut self,
+        row: i32,
+        col: i32,
+        new: String,
+        editor: &mut dyn WidgetExt,
+    ) -> bool {
+        if let CellEditor::Mask(m) = self.col_editor(col) {
+            if !mask::mask_complete(&m, &new) {
+                let e = format!("Expected {m}");
+                editor.set_tooltip(&e);
+                *self.edit_error.try_lock().unwrap() = Some(e);
+                return false;
+            }
+        }
+        let validator = self.validator.clone();
+        if let Some(validate) = &mut *validator.try_lock().unwrap() {
+            if let Err(e) = validate(row, col, &new) {
+                editor.set_tooltip(&e);
+                *self.edit_error.try_lock().unwrap() = Some(e);
+                return false;
+            }
+        }
+        if self.edit_error.try_lock().unwrap().take().is_some() {
+            editor.set_tooltip("");
+        }
+        if self.cell_value(row, col) == new {
+            // Nothing to store, e.g. the editor lost the focus without changes
+            return true;
+        }
+        // The update callback runs with the data and the model unlocked, it may read the table
+        let model_old = self
+            .model
+            .try_lock()
+            .unwrap()
+            .as_ref()
+            .map(|m| m.cell(row, col));
+        let old = match model_old {
+            Some(old) => {
+                self.on_update_callback.try_lock().unwrap()(row, col, new.clone());
+                if let Some(m) = self.model.try_lock().unwrap().as_mut() {
+                    m.set_cell(row, col, &new);
+                }
+                old
+            }
+            None => {
+                let data = self.data.try_lock().unwrap();
+                let target = &data[row as usize][col as usize];
+                if target.locked {
+                    // Being updated in the background, keep the edit open until it's unlocked
+                    return false;
+                }
+                let old = target.label.clone();
+                drop(data);
+                self.on_update_callback.try_lock().unwrap()(row, col, new.clone());
+                self.data.try_lock().unwrap()[row as usize][col as usize].set_text(new.clone());
+                old
+            }
+        };
+        Self::notify_col_watchers(&self.col_watchers, row, col, &new);
+        self.mark_changed();
+        self.table.redraw();
+        let depth = self.opts.try_lock().unwrap().undo_depth;
+        self.undo_stack
+            .try_lock()
+            .unwrap()
+            .push(row, col, old.clone(), new.clone(), depth);
+        let on_cell_change = self.on_cell_change.clone();
+        if let Some(cb) = &mut *on_cell_change.try_lock().unwrap() {
+            cb(self, row, col, &old, &new);
+        }
+        true
+    }
+
+    /// Returns whether an inline editor is open
+    pub fn is_editing(&self) -> bool {
+        self.inp.as_ref().is_some_and(|i| i.visible())
+            || self.choice.as_ref().is_some_and(|c| c.visible())
+            || self.text_editor.as_ref().is_some_and(|e| e.visible())
+    }
+
+    /// Commit the value of the open editor into its cell, as if Enter was pressed.
+    /// Returns whether the edit was committed, an edit of a locked cell stays open
+    pub fn commit_edit(&mut self) -> bool {
+        if let Some(inp) = self.inp.as_mut().filter(|i| i.visible()) {
+            inp.do_callback();
+            return !inp.visible();
+        }
+        if let Some(choice) = self.choice.as_mut().filter(|c| c.visible()) {
+            choice.do_callback();
+            return !choice.visible();
+        }
+        if let Some(editor) = self.text_editor.as_mut().filter(|e| e.visible()) {
+            editor.do_callback();
+            return !editor.visible();
+        }
+        false
+    }
+
+    /// Set a validator run on the edited value before it's stored. On error the editor
+    /// stays open and the message is shown as its tooltip, see `edit_error()`
+    pub fn set_validator<F: FnMut(i32, i32, &str) -> Result<(), String> + Send + 'static>(
+        &mut self,
+        f: F,
+    ) {
+        *self.validator.try_lock().unwrap() = Some(Box::new(f));
+    }
+
+    /// Returns the message of the validator refusing the open edit
+    pub fn edit_error(&self) -> Option<String> {
+        self.edit_error.try_lock().unwrap().clone()
+    }
+
+    /// Close the open editor, discarding its value
+    pub fn cancel_edit(&mut self) {
+        let error = self.edit_error.try_lock().unwrap().take().is_some();
+        if let Some(inp) = self.inp.as_mut().filter(|i| i.visible()) {
+            if error {
+                inp.set_tooltip("");
+            }
+            inp.set_value("");
+            inp.hide();
+            self.table.redraw();
+        }
+        if let Some(choice) = self.choice.as_mut().filter(|c| c.visible()) {
+            if error {
+                choice.set_tooltip("");
+            }
+            choice.hide();
+            self.table.redraw();
+        }
+        if let Some(editor) = self.text_editor.as_mut().filter(|e| e.visible()) {
+            if error {
+                editor.set_tooltip("");
+            }
+            editor.hide();
+            self.table.redraw();
+        }
+    }
+
+    // Commits or cancels the open edit following `TableOpts::edit_focus_loss`.
+    // An edit which can't be committed is cancelled, its cell moved away from the editor
+    fn leave_editor(&mut self) {
+        let policy = self.opts.try_lock().unwrap().edit_focus_loss;
+        if policy == FocusLossPolicy::Cancel || !self.commit_edit() {
+            self.cancel_edit();
+        }
+    }
+
+    // Moves the open editor onto its cell after the table scrolled or resized,
+    // the edit is left once the cell isn't entirely in view
+    pub(crate) fn follow_editor(&mut self) {
+        let (x0, y0, x1, y1) = Self::cells_area(&self.table);
+        let rect = Self::focus_of(&self.table)
+            .and_then(|(row, col)| self.table.find_cell(table::TableContext::Cell, row, col))
+            .filter(|&(x, y, w, h)| x >= x0 && y >= y0 && x + w <= x1 && y + h <= y1);
+        let Some((x, y, w, h)) = rect else {
+            self.leave_editor();
+            return;
+        };
+        if let Some(inp) = self.inp.as_mut().filter(|i| i.visible()) {
+            let h = h.max(inp.h());
+            inp.resize(x, y, w, h);
+            // The suggestions stay under the editor
+            if let Some(list) = self.completions.as_mut().filter(|l| l.visible()) {
+                let h = list.h();
+                Self::place_suggestions(inp, list, h);
+            }
+        }
+        if let Some(choice) = self.choice.as_mut().filter(|c| c.visible()) {
+            let h = h.max(choice.h());
+            choice.resize(x, y, w, h);
+        }
+        if let Some(editor) = self.text_editor.as_mut().filter(|e| e.visible()) {
+            let h = h.max(editor.h());
+            editor.resize(x, y, w, h);
+        }
+        // Repaints the cells under the editor's previous position
+        self.table.redraw();
+    }
+
+    // Opens the column's editor over the cell with its text, None when the cell is missing or
+    // locked. Check cells are toggled instead. Returns whether the click was handled.
+    // A middle-click pastes the primary selection into text editors
+    pub(crate) fn open_editor(
+        &mut self,
+        text: Option<String>,
+        cell: &CellData,
+        min_h: i32,
+    ) -> bool {
+        let Some(text) = text else {
+            return false;
+        };
+        if let Some(choice) = self.choice.as_mut() {
+            choice.hide();
+        }
+        if let Some(inp) = self.inp.as_mut() {
+            inp.hide();
+        }
+        if let Some(editor) = self.text_editor.as_mut() {
+            editor.hide();
+        }
+        let (x, y, w, h) = (cell.x, cell.y, cell.w, cell.h.max(min_h));
+        let editor = if self.toggles(cell.row, cell.col) {
+            // Checkboxes are toggled whatever the column's editor
+            CellEditor::Check
+        } else {
+            self.col_editor(cell.col)
+        };
+        match editor {
+            CellEditor::Check => {
+                let checked = Self::checked_text(&text);
+                let mut table = self.table.clone();
+                self.commit_value(cell.row, cell.col, (!checked).to_string(), &mut table);
+                true
+            }
+            CellEditor::Choice(items) => {
+                let Some(choice) = self.choice.as_mut() else {
+                    return false;
+                };
+                choice.clear();
+                for item in &items {
+                    choice.add_choice(&menu_label(item));
+                }
+                choice.set_value(
+                    items
+                        .iter()
+                        .position(|v| *v == text)
+                        .map_or(-1, |i| i as i32),
+                );
+                choice.resize(x, y, w, h);
+                choice.show();
+                choice.take_focus().ok();
+                choice.redraw();
+                true
+            }
+            CellEditor::Multiline => {
+                let Some(editor) = self.text_editor.as_mut() else {
+                    return false;
+                };
+                // Room for a few lines below the cell
+                editor.resize(x, y, w, h.max(min_h * 4));
+                if let Some(mut buf) = editor.buffer() {
+                    buf.set_text(&text);
+                }
+                editor.set_insert_position(text.len() as i32);
+                editor.show();
+                editor.take_focus().ok();
+                editor.redraw();
+                true
+            }
+            kind => {
+                let Some(inp) = self.inp.as_mut() else {
+                    return false;
+                };
+                inp.set_type(match kind {
+                    CellEditor::Int => input::InputType::Int,
+                    CellEditor::Float => input::InputType::Float,
+                    _ => input::InputType::Normal,
+                });
+                inp.resize(x, y, w, h);
+                inp.set_value(&text);
+                inp.show();
+                inp.take_focus().ok();
+                if app::event_mouse_button() == app::MouseButton::Middle {
+                    let end = inp.value().len() as i32;
+                    inp.set_position(end).ok();
+                    app::paste_text2(inp);
+                }
+                inp.redraw();
+                true
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+// Errors of the fallible methods, and the fallible variants of the indexing methods
+
+use super::{OverflowPolicy, SmartTable, TableLimit};
+use std::fmt;
+
+/// Why a fallible table operation failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableError {
+    /// The row index is out of range
+    RowOutOfRange(i32),
+    /// The column index is out of range
+    ColOutOfRange(i32),
+    /// The number of values doesn't match the number of columns, or rows
+    DimensionMismatch { expected: usize, found: usize },
+    /// The append was rejected by the limits set with `SmartTable::set_limits()`
+    LimitReached(TableLimit),
+    /// The table shows a model, whose rows and columns the `try_` methods adding or removing
+    /// them can't change
+    ModelSet,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::RowOutOfRange(row) => write!(f, "row {row} is out of range"),
+            TableError::ColOutOfRange(col) => write!(f, "column {col} is out of range"),
+            TableError::DimensionMismatch { expected, found } => {
+                write!(f, "expected {expected} values, found {found}")
+            }
+            TableError::LimitReached(TableLimit::Rows) => write!(f, "the row limit is reached"),
+            TableError::LimitReached(TableLimit::Cols) => {
+                write!(f, "the column limit is reached")
+            }
+            TableError::ModelSet => write!(f, "the table shows a model"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+impl SmartTable {
+    // Rows and columns of the model or the data
+    fn data_dims(&self) -> (i32, i32) {
+        if let Some(m) = self.model.try_lock().unwrap().as_ref() {
+            return (m.row_count(), m.col_count());
+        }
+        (
+            self.data.try_lock().unwrap().len() as i32,
+            self.col_headers.try_lock().unwrap().len() as i32,
+        )
+    }
+
+    fn check_own_data(&self) -> Result<(), TableError> {
+        if self.has_model() {
+            Err(TableError::ModelSet)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_row(&self, row: i32) -> Result<(), TableError> {
+        if (0..self.data_dims().0).contains(&row) {
+            Ok(())
+        } else {
+            Err(TableError::RowOutOfRange(row))
+        }
+    }
+
+    fn check_col(&self, col: i32) -> Result<(), TableError> {
+        if (0..self.data_dims().1).contains(&col) {
+            Ok(())
+        } else {
+            Err(TableError::ColOutOfRange(col))
+        }
+    }
+
+    /// Get the cell value, or an error when the cell doesn't exist
+    pub fn try_cell_value(&self, row: i32, col: i32) -> Result<String, TableError> {
+        self.check_row(row)?;
+        self.check_col(col)?;
+        Ok(self.cell_value(row, col))
+    }
+
+    /// Set the cell value, or return an error when the cell doesn't exist
+    pub fn try_set_cell_value(&mut self, row: i32, col: i32, val: &str) -> Result<(), TableError> {
+        self.check_row(row)?;
+        self.check_col(col)?;
+        self.set_cell_value(row, col, val);
+        Ok(())
+    }
+
+    /// Remove the row, or return an error when it doesn't exist
+    pub fn try_remove_row(&mut self, row: i32) -> Result<(), TableError> {
+        self.check_own_data()?;
+        self.check_row(row)?;
+        self.remove_row(row);
+        Ok(())
+    }
+
+    /// Remove the column, or return an error when it doesn't exist
+    pub fn try_remove_col(&mut self, col: i32) -> Result<(), TableError> {
+        self.check_own_data()?;
+        self.check_col(col)?;
+        self.remove_col(col);
+        Ok(())
+    }
+
+    pub(crate) fn check_len(expected: i32, found: usize) -> Result<(), TableError> {
+        if expected as usize == found {
+            Ok(())
+        } else {
+            Err(TableError::DimensionMismatch {
+                expected: expected as usize,
+                found,
+            })
+        }
+    }
+
+    // Whether an append would be rejected by the limits, `count` being the current count
+    fn check_limit(&self, limit: TableLimit, count: i32) -> Result<(), TableError> {
+        let limits = *self.limits.try_lock().unwrap();
+        let max = match limit {
+            TableLimit::Rows => limits.max_rows,
+            TableLimit::Cols => limits.max_cols,
+        };
+        if limits.policy == OverflowPolicy::Reject && max.is_some_and(|m| count as usize >= m) {
+            self.call_limit_cb(limit);
+            Err(TableError::LimitReached(limit))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Append a row, or return an error when the number of values doesn't match the number
+    /// of columns or the row limit rejects it
+    pub fn try_append_row(&mut self, row_header: &str, vals: &[&str]) -> Result<(), TableError> {
+        self.check_own_data()?;
+        let (rows, cols) = self.data_dims();
+        Self::check_len(cols, vals.len())?;
+        self.check_limit(TableLimit::Rows, rows)?;
+        self.append_row(row_header, vals);
+        Ok(())
+    }
+
+    /// Insert a row, or return an error when the index is out of range or the number of values
+    /// doesn't match the number of columns
+    pub fn try_insert_row(
+        &mut self,
+        row: i32,
+        row_header: &str,
+        vals: &[&str],
+    ) -> Result<(), TableError> {
+        self.check_own_data()?;
+        let (rows, cols) = self.data_dims();
+        if !(0..=rows).contains(&row) {
+            return Err(TableError::RowOutOfRange(row));
+        }
+        Self::check_len(cols, vals.len())?;
+        self.insert_row(row, row_header, vals);
+        Ok(())
+    }
+
+    /// Append a column, or return an error when the number of values doesn't match the number
+    /// of rows or the column limit rejects it
+    pub fn try_append_col(&mut self, col_header: &str, vals: &[&str]) -> Result<(), TableError> {
+        self.check_own_data()?;
+        let (rows, cols) = self.data_dims();
+        Self::check_len(rows, vals.len())?;
+        self.check_limit(TableLimit::Cols, cols)?;
+        self.append_col(col_header, vals);
+        Ok(())
+    }
+
+    /// Insert a column, or return an error when the index is out of range or the number of
+    /// values doesn't match the number of rows
+    pub fn try_insert_col(
+        &mut self,
+        col: i32,
+        col_header: &str,
+        vals: &[&str],
+    ) -> Result<(), TableError> {
+        self.check_own_data()?;
+        let (rows, cols) = self.data_dims();
+        if !(0..=cols).contains(&col) {
+            return Err(TableError::ColOutOfRange(col));
+        }
+        Self::check_len(rows, vals.len())?;
+        self.insert_col(col, col_header, vals);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lengths() {
+        assert_eq!(SmartTable::check_len(3, 3), Ok(()));
+        assert_eq!(
+            SmartTable::check_len(3, 2),
+            Err(TableError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,274 @@
+// Row filtering, a view of the data rows passing a predicate
+
+use super::{footer::FooterCache, tree::TreeNodes, CellMatrix, Model, SmartTable, TableModel};
+use fltk::{prelude::*, table};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+type RowPredicate = Box<dyn Fn(i32, &[String]) -> bool + Send>;
+
+#[derive(Default)]
+pub(crate) struct RowFilter {
+    pred: Option<RowPredicate>,
+    // Text the cells of a column contain, ignoring case, by column
+    pub(crate) col_text: BTreeMap<i32, String>,
+    // Values unchecked in a column's filter dropdown, by column
+    pub(crate) col_excluded: BTreeMap<i32, HashSet<String>>,
+    // Rows per page and the shown page, when paging
+    pub(crate) page: Option<(i32, i32)>,
+    // The tree's nodes while some are collapsed, hiding their descendants
+    pub(crate) tree: Option<Arc<Mutex<TreeNodes>>>,
+    // Number of rows passing the predicate, on all the pages
+    pub(crate) passing: i32,
+    // The data index of each displayed row
+    pub(crate) view: Vec<i32>,
+}
+
+impl RowFilter {
+    // Sets the rows passing the filter and the rows shown, those which aren't folded away,
+    // on the shown page
+    pub(crate) fn update_view(&mut self, data: &CellMatrix, model: Option<&dyn TableModel>) {
+        let (rows, cols) = SmartTable::source_size(data, model);
+        let col_text: Vec<(i32, String)> = self
+            .col_text
+            .iter()
+            .map(|(col, txt)| (*col, txt.to_lowercase()))
+            .collect();
+        let folded = self
+            .tree
+            .as_ref()
+            .map(|tree| SmartTable::folded_rows(&tree.try_lock().unwrap(), rows as usize));
+        let passing: Vec<i32> = (0..rows)
+            .filter(|&i| {
+                if folded.as_ref().is_some_and(|folded| folded[i as usize]) {
+                    return false;
+                }
+                let cell = |col: i32| SmartTable::source_cell(data, model, i, col);
+                let contains = |(col, txt): &(i32, String)| {
+                    cell(*col).is_some_and(|c| c.label.to_lowercase().contains(txt.as_str()))
+                };
+                if !col_text.iter().all(contains) {
+                    return false;
+                }
+                let excluded = |(col, values): (&i32, &HashSet<String>)| {
+                    cell(*col).is_some_and(|c| values.contains(&c.label))
+                };
+                if self.col_excluded.iter().any(excluded) {
+                    return false;
+                }
+                match &self.pred {
+                    Some(pred) => {
+                        let vals: Vec<String> = (0..cols)
+                            .map(|col| cell(col).map(|c| c.label.clone()).unwrap_or_default())
+                            .collect();
+                        pred(i, &vals)
+                    }
+                    None => true,
+                }
+            })
+            .collect();
+        self.passing = passing.len() as i32;
+        self.view = match &mut self.page {
+            Some((size, page)) => {
+                // The page is kept within the pages left, e.g. after removing rows
+                let last = (self.passing - 1).max(0) / *size;
+                *page = (*page).min(last);
+                passing
+                    .into_iter()
+                    .skip((*page * *size) as usize)
+                    .take(*size as usize)
+                    .collect()
+            }
+            None => passing,
+        };
+    }
+}
+
+impl SmartTable {
+    /// Only display the rows for which `pred` returns true, given the row index and the row's
+    /// values. The data isn't modified, the table's indices such as the selection refer to the
+    /// displayed rows while the cell accessors keep using data indices,
+    /// see `view_to_data_row()` and `data_to_view_row()`.
+    /// Rows added or removed are filtered, call `refilter()` after modifying cell values
+    pub fn set_row_filter<F: Fn(i32, &[String]) -> bool + Send + 'static>(&mut self, pred: F) {
+        self.edit_filter(|f| {
+            f.pred = Some(Box::new(pred));
+            // Paging restarts at the first page
+            if let Some(page) = f.page.as_mut() {
+                page.1 = 0;
+            }
+        });
+        self.refilter();
+    }
+
+    /// Display all the rows again
+    pub fn clear_filter(&mut self) {
+        self.edit_filter(|f| f.pred = None);
+        self.refilter();
+    }
+
+    /// Reapply the row filter to the current data
+    pub fn refilter(&mut self) {
+        let data = self.data.try_lock().unwrap();
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.table.unset_selection();
+        self.table.redraw();
+    }
+
+    /// Returns whether a row filter is set
+    pub fn is_filtered(&self) -> bool {
+        self.filter
+            .try_lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|f| f.pred.is_some())
+    }
+
+    // Changes a part of the filter, which is dropped once it neither filters, pages nor folds
+    // rows. The rows are synced by the caller
+    pub(crate) fn edit_filter(&self, edit: impl FnOnce(&mut RowFilter)) {
+        let mut filter = self.filter.try_lock().unwrap();
+        let mut f = filter.take().unwrap_or_default();
+        edit(&mut f);
+        let used = f.pred.is_some()
+            || !f.col_text.is_empty()
+            || !f.col_excluded.is_empty()
+            || f.page.is_some()
+            || f.tree.is_some();
+        *filter = used.then_some(f);
+    }
+
+    // Moves the columns' text filters and excluded values to their new index, dropping those
+    // of removed columns. Returns whether one was dropped, which changes the rows passing
+    pub(crate) fn remap_filter_cols(&mut self, f: &impl Fn(i32) -> Option<i32>) -> bool {
+        let mut dropped = false;
+        let mut remap = |col| {
+            let col = f(col);
+            dropped |= col.is_none();
+            col
+        };
+        self.edit_filter(|filter| {
+            filter.col_text = std::mem::take(&mut filter.col_text)
+                .into_iter()
+                .filter_map(|(col, txt)| remap(col).map(|col| (col, txt)))
+                .collect();
+            filter.col_excluded = std::mem::take(&mut filter.col_excluded)
+                .into_iter()
+                .filter_map(|(col, values)| remap(col).map(|col| (col, values)))
+                .collect();
+        });
+        self.sync_filter_inputs();
+        dropped
+    }
+
+    /// Maps a displayed row to its data row
+    pub fn view_to_data_row(&self, row: i32) -> i32 {
+        Self::data_row(&self.filter.try_lock().unwrap(), row)
+    }
+
+    /// Maps a data row to the row displaying it, None if it's filtered out
+    pub fn data_to_view_row(&self, row: i32) -> Option<i32> {
+        match &*self.filter.try_lock().unwrap() {
+            Some(f) => f.view.binary_search(&row).ok().map(|r| r as i32),
+            None => Some(row),
+        }
+    }
+
+    // Maps a displayed row to its data row, rows outside the view are left as is
+    pub(crate) fn data_row(filter: &Option<RowFilter>, row: i32) -> i32 {
+        filter
+            .as_ref()
+            .and_then(|f| f.view.get(row as usize).copied())
+            .unwrap_or(row)
+    }
+
+    // Sets the rows of the inner table to the data rows, or to the rows passing the filter
+    // which aren't folded away, on the shown page
+    pub(crate) fn sync_rows(
+        t: &mut table::TableRow,
+        filter: &Mutex<Option<RowFilter>>,
+        model: &Mutex<Option<Model>>,
+        data: &CellMatrix,
+        footer: &Mutex<FooterCache>,
+    ) {
+        footer.lock().unwrap().take();
+        let model = model.try_lock().unwrap();
+        let model = model.as_deref();
+        let rows = Self::source_size(data, model).0;
+        let mut filter = filter.try_lock().unwrap();
+        let Some(f) = filter.as_mut() else {
+            t.set_rows(rows);
+            Self::clamp_selection(t);
+            return;
+        };
+        f.update_view(data, model);
+        t.set_rows(f.view.len() as i32);
+        Self::clamp_selection(t);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cell;
+
+    fn data() -> CellMatrix {
+        [
+            ["Apple", "1"],
+            ["banana", "2"],
+            ["apricot", "3"],
+            ["cherry", "4"],
+        ]
+        .iter()
+        .map(|r| r.iter().map(|v| Cell::from_string(v.to_string())).collect())
+        .collect()
+    }
+
+    fn view(f: &mut RowFilter) -> Vec<i32> {
+        f.update_view(&data(), None);
+        f.view.clone()
+    }
+
+    #[test]
+    fn col_filters() {
+        let mut f = RowFilter::default();
+        assert_eq!(view(&mut f), [0, 1, 2, 3]);
+        f.col_text.insert(0, "AP".into());
+        assert_eq!(view(&mut f), [0, 2]);
+        f.col_excluded.insert(1, HashSet::from(["3".to_string()]));
+        assert_eq!(view(&mut f), [0]);
+        assert_eq!(f.passing, 1);
+    }
+
+    #[test]
+    fn predicate() {
+        let mut f = RowFilter {
+            pred: Some(Box::new(|row, vals| row > 0 && vals[1] != "3")),
+            ..Default::default()
+        };
+        assert_eq!(view(&mut f), [1, 3]);
+    }
+
+    #[test]
+    fn pages() {
+        let mut f = RowFilter {
+            page: Some((3, 0)),
+            ..Default::default()
+        };
+        assert_eq!(view(&mut f), [0, 1, 2]);
+        f.page = Some((3, 1));
+        assert_eq!(view(&mut f), [3]);
+        assert_eq!(f.passing, 4);
+        // Past the last page, e.g. after rows were removed
+        f.page = Some((3, 5));
+        assert_eq!(view(&mut f), [3]);
+        assert_eq!(f.page, Some((3, 1)));
+    }
+}
@@ -0,0 +1,191 @@
+// The filter row, inputs under the column headers filtering the rows by the text of their cells
+
+use super::{ColProps, SmartTable};
+use fltk::{
+    app,
+    enums::*,
+    input,
+    prelude::{GroupExt, InputExt, TableExt, WidgetBase, WidgetExt},
+    table::{self, TableContext},
+    window,
+};
+
+// Narrower inputs are hidden, e.g. at the edges of the view
+const MIN_INPUT_WIDTH: i32 = 12;
+
+pub(crate) type FilterCallback = Box<dyn FnMut(&mut SmartTable) + Send>;
+
+// The inputs, one per column, and the height of the band they take under the column headers
+pub(crate) struct FilterRow {
+    inputs: Vec<input::Input>,
+    pub(crate) band: i32,
+}
+
+impl SmartTable {
+    /// Show or hide a row of inputs under the column headers. Typing into a column's input
+    /// only displays the rows whose cell in the column contains the text, ignoring case,
+    /// the columns' filters and the row filter all have to pass. The table must be in a window
+    pub fn set_show_filter_row(&mut self, show: bool) {
+        let mut filter_row = self.filter_row.try_lock().unwrap();
+        match (filter_row.take(), show) {
+            (None, true) => {
+                let band = self.table.col_header_height();
+                self.table.set_col_header_height(2 * band);
+                *filter_row = Some(FilterRow {
+                    inputs: vec![],
+                    band,
+                });
+                drop(filter_row);
+                self.build_filter_inputs();
+            }
+            (Some(row), false) => {
+                for inp in row.inputs {
+                    WidgetBase::delete(inp);
+                }
+                let h = self.table.col_header_height();
+                self.table.set_col_header_height(h - row.band);
+                drop(filter_row);
+                self.edit_filter(|f| f.col_text.clear());
+                self.refilter();
+            }
+            (row, _) => *filter_row = row,
+        }
+        self.table.redraw();
+    }
+
+    /// Filter the rows by the text the column's cells contain, ignoring case, as if typed
+    /// into the filter row. An empty text removes the column's filter
+    pub fn set_col_filter(&mut self, col: i32, text: &str) {
+        if let Some(row) = &mut *self.filter_row.try_lock().unwrap() {
+            if let Some(inp) = row.inputs.get_mut(col as usize) {
+                if inp.value() != text {
+                    inp.set_value(text);
+                }
+            }
+        }
+        self.edit_filter(|f| {
+            if text.is_empty() {
+                f.col_text.remove(&col);
+            } else {
+                f.col_text.insert(col, text.to_string());
+            }
+        });
+        self.refilter();
+    }
+
+    /// Returns the columns filtered by text and their text, see `set_col_filter()`
+    pub fn active_filters(&self) -> Vec<(i32, String)> {
+        self.filter
+            .try_lock()
+            .unwrap()
+            .as_ref()
+            .map(|f| f.col_text.iter().map(|(c, t)| (*c, t.clone())).collect())
+            .unwrap_or_default()
+    }
+
+    /// Remove the filters of all the columns
+    pub fn clear_col_filters(&mut self) {
+        for (col, _) in self.active_filters() {
+            self.set_col_filter(col, "");
+        }
+    }
+
+    /// Set a callback called after typing into the filter row or picking a value in a filter
+    /// dropdown refilters the rows
+    pub fn set_on_filter_changed<F: FnMut(&mut SmartTable) + Send + 'static>(&mut self, cb: F) {
+        *self.on_filter_changed.try_lock().unwrap() = Some(Box::new(cb));
+    }
+
+    // Shows the filter text of each column in its input, e.g. after the columns moved
+    pub(crate) fn sync_filter_inputs(&self) {
+        let texts = self.active_filters();
+        if let Some(row) = &mut *self.filter_row.try_lock().unwrap() {
+            for (col, inp) in row.inputs.iter_mut().enumerate() {
+                let txt = texts
+                    .iter()
+                    .find(|(c, _)| *c == col as i32)
+                    .map_or("", |(_, t)| t.as_str());
+                if inp.value() != txt {
+                    inp.set_value(txt);
+                }
+            }
+        }
+    }
+
+    // Creates an input per column, added to the window like the editor
+    fn build_filter_inputs(&mut self) {
+        let Some(win) = self.table.top_window() else {
+            return;
+        };
+        let mut win = window::Window::from_dyn_widget_ptr(win.as_widget_ptr()).unwrap();
+        let texts = self.active_filters();
+        let mut filter_row = self.filter_row.try_lock().unwrap();
+        let Some(row) = filter_row.as_mut() else {
+            return;
+        };
+        for inp in row.inputs.drain(..) {
+            WidgetBase::delete(inp);
+        }
+        for col in 0..self.column_count() {
+            let mut inp = input::Input::default();
+            win.add(&inp);
+            inp.hide();
+            inp.set_trigger(CallbackTrigger::Changed);
+            if let Some((_, txt)) = texts.iter().find(|(c, _)| *c == col) {
+                inp.set_value(txt);
+            }
+            inp.set_callback({
+                let mut this = self.clone();
+                move |i| {
+                    this.set_col_filter(col, &i.value());
+                    let cb = this.on_filter_changed.clone();
+                    if let Some(cb) = &mut *cb.try_lock().unwrap() {
+                        cb(&mut this);
+                    };
+                }
+            });
+            row.inputs.push(inp);
+        }
+    }
+
+    // Moves the inputs over the bottom of their column headers, clipped to the cells area.
+    // The inputs are rebuilt once drawing ends when the columns changed
+    pub(crate) fn place_filter_inputs(
+        &self,
+        t: &table::TableRow,
+        row: &mut FilterRow,
+        col_props: &[ColProps],
+        rtl: bool,
+    ) {
+        if row.inputs.len() != col_props.len() {
+            let mut this = self.clone();
+            app::add_timeout3(0.0, move |_| this.build_filter_inputs());
+            return;
+        }
+        let (x0, _, x1, _) = Self::cells_area(t);
+        let ncols = col_props.len() as i32;
+        for (col, inp) in row.inputs.iter_mut().enumerate() {
+            let view_col = Self::display_col(rtl, ncols, col as i32);
+            let rect = t
+                .find_cell(TableContext::ColHeader, 0, view_col)
+                .filter(|_| t.col_header() && col_props[col].hidden.is_none())
+                .map(|(x, y, w, h)| {
+                    let (l, r) = (x.max(x0), (x + w).min(x1));
+                    (l, y + h - row.band, r - l, row.band)
+                })
+                .filter(|r| r.2 >= MIN_INPUT_WIDTH);
+            match rect {
+                Some((x, y, w, h)) => {
+                    if (inp.x(), inp.y(), inp.w(), inp.h()) != (x, y, w, h) || !inp.visible() {
+                        inp.resize(x, y, w, h);
+                        inp.show();
+                    }
+                    // Drawn again over the headers
+                    inp.redraw();
+                }
+                None if inp.visible() => inp.hide(),
+                None => (),
+            }
+        }
+    }
+}
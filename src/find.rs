@@ -0,0 +1,302 @@
+// Finding cells by their text, with the matches highlighted
+
+use super::{pattern::Regex, SmartTable};
+use fltk::prelude::{TableExt, WidgetExt};
+use std::collections::HashSet;
+
+/// How `SmartTable::find()` matches the text of the cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FindOpts {
+    /// Tell uppercase and lowercase letters apart
+    pub case_sensitive: bool,
+    /// Match the whole text of the cell rather than a part of it
+    pub whole_cell: bool,
+    /// The text is a regular expression, made of literals, `.`, `[...]` classes, `\d`, `\w`,
+    /// `\s`, the `*`, `+` and `?` repeats, and the `^` and `$` anchors. Alternation `|`, groups
+    /// `(...)` and counted repeats `{n}` aren't supported and find nothing
+    pub regex: bool,
+}
+
+// The regular expression without its own anchors, an escaped trailing `\$` is kept
+fn strip_anchors(text: &str) -> &str {
+    let text = text.strip_prefix('^').unwrap_or(text);
+    match text.strip_suffix('$') {
+        Some(rest) if (rest.len() - rest.trim_end_matches('\\').len()) % 2 == 0 => rest,
+        _ => text,
+    }
+}
+
+// The searched text compiled with its options
+pub(crate) enum Matcher {
+    Text {
+        needle: Vec<char>,
+        icase: bool,
+        whole: bool,
+    },
+    Regex(Regex),
+}
+
+impl Matcher {
+    // None for an empty text or an invalid regular expression
+    pub(crate) fn new(text: &str, opts: FindOpts) -> Option<Matcher> {
+        if text.is_empty() {
+            return None;
+        }
+        let icase = !opts.case_sensitive;
+        if opts.regex {
+            let anchored = if opts.whole_cell {
+                format!("^{}$", strip_anchors(text))
+            } else {
+                text.to_string()
+            };
+            return Regex::new(&anchored, icase).map(Matcher::Regex);
+        }
+        Some(Matcher::Text {
+            needle: text.chars().collect(),
+            icase,
+            whole: opts.whole_cell,
+        })
+    }
+
+    // The non-empty matches in the text, as start and end character indices
+    pub(crate) fn spans(&self, text: &[char]) -> Vec<(usize, usize)> {
+        let mut spans = vec![];
+        let mut from = 0;
+        while from <= text.len() {
+            let found = match self {
+                Matcher::Text {
+                    needle,
+                    icase,
+                    whole,
+                } => {
+                    let at = |s: &usize| {
+                        text[*s..].iter().zip(needle).all(|(a, b)| {
+                            a == b || (*icase && a.to_lowercase().eq(b.to_lowercase()))
+                        })
+                    };
+                    if *whole {
+                        return if text.len() == needle.len() && at(&0) {
+                            vec![(0, text.len())]
+                        } else {
+                            vec![]
+                        };
+                    }
+                    (from..=text.len())
+                        .take_while(|s| s + needle.len() <= text.len())
+                        .find(at)
+                        .map(|s| (s, s + needle.len()))
+                }
+                Matcher::Regex(re) => re.find_at(text, from),
+            };
+            match found {
+                Some((s, e)) if e > s => {
+                    spans.push((s, e));
+                    from = e;
+                }
+                // An empty match, e.g. of `a*`, is skipped over
+                Some((s, _)) => from = s + 1,
+                None => break,
+            }
+        }
+        spans
+    }
+
+    pub(crate) fn is_match(&self, text: &str) -> bool {
+        !self.spans(&text.chars().collect::<Vec<_>>()).is_empty()
+    }
+
+    // The text with its matches replaced, None when nothing matched
+    pub(crate) fn replace(&self, text: &str, with: &str) -> Option<String> {
+        let chars: Vec<char> = text.chars().collect();
+        let spans = self.spans(&chars);
+        if spans.is_empty() {
+            return None;
+        }
+        let mut out = String::new();
+        let mut last = 0;
+        for (s, e) in spans {
+            out.extend(&chars[last..s]);
+            out.push_str(with);
+            last = e;
+        }
+        out.extend(&chars[last..]);
+        Some(out)
+    }
+}
+
+// The last search, whose matches are highlighted
+#[derive(Default)]
+pub(crate) struct FindState {
+    pub(crate) query: Option<(String, FindOpts)>,
+    pub(crate) matches: HashSet<(i32, i32)>,
+}
+
+impl SmartTable {
+    /// Find the cells whose text matches, as (row, col) pairs in data order.
+    /// The matches are highlighted in `TableOpts::find_color` until `clear_find()`,
+    /// and `find_next()` and `find_prev()` go through them
+    pub fn find(&mut self, text: &str, opts: FindOpts) -> Vec<(i32, i32)> {
+        let found = match Matcher::new(text, opts) {
+            Some(m) => self.matching_cells(&m),
+            None => vec![],
+        };
+        let mut state = self.found.try_lock().unwrap();
+        state.query = Some((text.to_string(), opts));
+        state.matches = found.iter().copied().collect();
+        drop(state);
+        self.table.redraw();
+        found
+    }
+
+    // The cells matching, in data order
+    pub(crate) fn matching_cells(&self, m: &Matcher) -> Vec<(i32, i32)> {
+        if let Some(model) = self.model.try_lock().unwrap().as_ref() {
+            return (0..model.row_count())
+                .flat_map(|r| (0..model.col_count()).map(move |c| (r, c)))
+                .filter(|(r, c)| m.is_match(&model.cell(*r, *c)))
+                .collect();
+        }
+        let data = self.data.try_lock().unwrap();
+        data.iter()
+            .enumerate()
+            .flat_map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, cell)| m.is_match(&cell.label))
+                    .map(move |(c, _)| (r as i32, c as i32))
+            })
+            .collect()
+    }
+
+    /// Select the next match of the last `find()` after the focused cell, wrapping around,
+    /// and scroll to it. The matches are searched again so edits are taken into account
+    pub fn find_next(&mut self) -> Option<(i32, i32)> {
+        self.step_find(true)
+    }
+
+    /// Select the previous match of the last `find()`, see `find_next()`
+    pub fn find_prev(&mut self) -> Option<(i32, i32)> {
+        self.step_find(false)
+    }
+
+    /// Remove the highlighting of the matches
+    pub fn clear_find(&mut self) {
+        *self.found.try_lock().unwrap() = FindState::default();
+        self.table.redraw();
+    }
+
+    /// Replace the matches in all the cells, returning the number of cells changed.
+    /// Locked and read-only cells are skipped, and tables backed by a model aren't changed.
+    /// The table is redrawn once and one `undo()` reverts all the replacements
+    pub fn replace_all(&mut self, pattern: &str, replacement: &str, opts: FindOpts) -> usize {
+        let Some(m) = Matcher::new(pattern, opts) else {
+            return 0;
+        };
+        if self.has_model() {
+            return 0;
+        }
+        let mut edits = vec![];
+        let data = self.data.clone();
+        for (r, row) in data.try_lock().unwrap().iter_mut().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                let (r, c) = (r as i32, c as i32);
+                if cell.locked || self.cell_read_only(r, c) {
+                    continue;
+                }
+                if let Some(new) = m.replace(&cell.label, replacement) {
+                    let old = std::mem::take(&mut cell.label);
+                    cell.set_text(new.clone());
+                    edits.push((r, c, old, new));
+                }
+            }
+        }
+        let count = edits.len();
+        self.record_step(edits);
+        self.refresh_find();
+        count
+    }
+
+    /// Replace the matches in the focused cell when it has some, then select the next match.
+    /// Returns the selected match, see `find_next()` and `replace_all()`
+    pub fn replace_next(
+        &mut self,
+        pattern: &str,
+        replacement: &str,
+        opts: FindOpts,
+    ) -> Option<(i32, i32)> {
+        let m = Matcher::new(pattern, opts)?;
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let focus = Self::focus_of(&self.table).and_then(|(r, c)| {
+            Some((
+                self.view_to_data_row(r),
+                Self::data_col(rtl, self.column_count(), c)?,
+            ))
+        });
+        if let Some((r, c)) =
+            focus.filter(|(r, c)| !self.has_model() && !self.cell_read_only(*r, *c))
+        {
+            let data = self.data.clone();
+            let mut data = data.try_lock().unwrap();
+            let cell = &mut data[r as usize][c as usize];
+            let new = m.replace(&cell.label, replacement).filter(|_| !cell.locked);
+            if let Some(new) = new {
+                let old = std::mem::take(&mut cell.label);
+                cell.set_text(new.clone());
+                drop(data);
+                self.record_step(vec![(r, c, old, new)]);
+            }
+        }
+        self.find(pattern, opts);
+        self.find_next()
+    }
+
+    // Searches the last query again after the data changed
+    fn refresh_find(&mut self) {
+        let query = self.found.try_lock().unwrap().query.clone();
+        if let Some((text, opts)) = query {
+            self.find(&text, opts);
+        }
+    }
+
+    fn step_find(&mut self, forward: bool) -> Option<(i32, i32)> {
+        let (text, opts) = self.found.try_lock().unwrap().query.clone()?;
+        let found = self.find(&text, opts);
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let cols = self.column_count();
+        // The matches shown, in view order
+        let mut shown: Vec<(i32, i32)> = found
+            .into_iter()
+            .filter(|(_, c)| self.is_col_visible(*c))
+            .filter_map(|(r, c)| Some((self.data_to_view_row(r)?, c)))
+            .collect();
+        shown.sort();
+        let focus =
+            Self::focus_of(&self.table).and_then(|(r, c)| Some((r, Self::data_col(rtl, cols, c)?)));
+        let next = match (focus, forward) {
+            (Some(f), true) => shown.iter().find(|m| **m > f).or(shown.first()),
+            (Some(f), false) => shown.iter().rev().find(|m| **m < f).or(shown.last()),
+            (None, true) => shown.first(),
+            (None, false) => shown.last(),
+        };
+        let &(view_row, col) = next?;
+        let view_col = Self::display_col(rtl, cols, col);
+        self.table
+            .set_selection(view_row, view_col, view_row, view_col);
+        self.ensure_visible(view_row, col);
+        self.table.redraw();
+        Some((self.view_to_data_row(view_row), col))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_stripped_once() {
+        assert_eq!(strip_anchors("^ab$"), "ab");
+        assert_eq!(strip_anchors("ab$$"), "ab$");
+        assert_eq!(strip_anchors(r"ab\$"), r"ab\$");
+        assert_eq!(strip_anchors(r"ab\\$"), r"ab\\");
+    }
+}
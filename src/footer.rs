@@ -0,0 +1,283 @@
+// The footer, a row pinned to the bottom of the cells showing an aggregate of each column
+
+use super::{filter, CellMatrix, ColProps, SmartTable, TableModel, TableOpts};
+use fltk::{
+    draw,
+    prelude::{TableExt, WidgetExt},
+    table::{self, TableContext},
+};
+use std::{fmt, sync::Arc};
+
+type AggregateFn = Arc<dyn Fn(&[String]) -> String + Send + Sync>;
+
+/// What the footer shows under a column, computed from the shown rows,
+/// see `SmartTable::set_col_aggregate()`
+#[derive(Clone, Default)]
+pub enum Aggregate {
+    /// Nothing
+    #[default]
+    None,
+    /// The sum of the numeric cells
+    Sum,
+    /// The average of the numeric cells
+    Avg,
+    /// The smallest numeric cell
+    Min,
+    /// The largest numeric cell
+    Max,
+    /// The number of cells which aren't empty
+    Count,
+    /// The text returned by the function, given the text of each cell
+    Custom(AggregateFn),
+}
+
+impl fmt::Debug for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Aggregate::None => f.write_str("None"),
+            Aggregate::Sum => f.write_str("Sum"),
+            Aggregate::Avg => f.write_str("Avg"),
+            Aggregate::Min => f.write_str("Min"),
+            Aggregate::Max => f.write_str("Max"),
+            Aggregate::Count => f.write_str("Count"),
+            Aggregate::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl Aggregate {
+    /// A custom aggregate computing the footer's text from the text of each cell
+    pub fn custom<F: Fn(&[String]) -> String + Send + Sync + 'static>(f: F) -> Self {
+        Aggregate::Custom(Arc::new(f))
+    }
+
+    fn is_none(&self) -> bool {
+        matches!(self, Aggregate::None)
+    }
+
+    fn apply(&self, vals: &[String]) -> String {
+        let nums = || vals.iter().filter_map(|v| v.trim().parse::<f64>().ok());
+        let num = match self {
+            Aggregate::None => return String::new(),
+            Aggregate::Count => return vals.iter().filter(|v| !v.is_empty()).count().to_string(),
+            Aggregate::Custom(f) => return f(vals),
+            // Folded from 0, an empty f64 sum is -0
+            Aggregate::Sum => Some(nums().fold(0.0, |sum, v| sum + v)),
+            Aggregate::Avg => {
+                let (n, sum) = nums().fold((0, 0.0), |(n, sum), v| (n + 1, sum + v));
+                (n > 0).then(|| sum / n as f64)
+            }
+            Aggregate::Min => nums().reduce(f64::min),
+            Aggregate::Max => nums().reduce(f64::max),
+        };
+        num.map(format_num).unwrap_or_default()
+    }
+}
+
+// Up to 4 decimals, without trailing zeros
+fn format_num(v: f64) -> String {
+    let s = format!("{v:.4}");
+    match s.trim_end_matches('0').trim_end_matches('.') {
+        // A tiny negative number rounded away
+        "-0" => "0".to_string(),
+        s => s.to_string(),
+    }
+}
+
+// The footer's text under each data column, computed once for the shown rows
+pub(crate) type FooterCache = Option<Vec<String>>;
+
+// The last row grown to make room for the footer: its row, height before and height given
+pub(crate) type FooterRoom = Option<(i32, i32, i32)>;
+
+impl SmartTable {
+    /// Show an aggregate of the column's shown rows in the footer, a row pinned to the bottom
+    /// of the cells. The footer shows while a column has an aggregate and follows the changes
+    /// to the data
+    pub fn set_col_aggregate(&mut self, col: i32, agg: Aggregate) {
+        self.col_props.try_lock().unwrap()[col as usize].aggregate = agg;
+        self.footer.lock().unwrap().take();
+        self.table.redraw();
+    }
+
+    /// Returns the column's aggregate
+    pub fn col_aggregate(&self, col: i32) -> Aggregate {
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .get(col as usize)
+            .map(|p| p.aggregate.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the footer's text under the column
+    pub fn footer_value(&self, col: i32) -> String {
+        let agg = self.col_aggregate(col);
+        let data = self.data.try_lock().unwrap();
+        let model = self.model.try_lock().unwrap();
+        let filter = self.filter.try_lock().unwrap();
+        Self::aggregate_col(
+            &agg,
+            self.table.rows(),
+            col,
+            &data,
+            model.as_deref(),
+            &filter,
+        )
+    }
+
+    fn aggregate_col(
+        agg: &Aggregate,
+        rows: i32,
+        col: i32,
+        data: &CellMatrix,
+        model: Option<&dyn TableModel>,
+        filter: &Option<filter::RowFilter>,
+    ) -> String {
+        if agg.is_none() {
+            return String::new();
+        }
+        let vals: Vec<String> = (0..rows)
+            .map(|r| {
+                let row = Self::data_row(filter, r);
+                match model {
+                    Some(m) => m.cell(row, col),
+                    None => data[row as usize][col as usize].label.clone(),
+                }
+            })
+            .collect();
+        agg.apply(&vals)
+    }
+
+    // Height of the footer, 0 while no column has an aggregate
+    pub(crate) fn footer_height(t: &table::TableRow, col_props: &[ColProps]) -> i32 {
+        if col_props.iter().all(|p| p.aggregate.is_none()) {
+            return 0;
+        }
+        t.col_header_height()
+    }
+
+    // Grows the last row by the footer's height so scrolling to the bottom shows it above the
+    // footer. The row grown before shrinks back once it's no longer the last one
+    pub(crate) fn fit_footer_room(t: &mut table::TableRow, fh: i32, room: &mut FooterRoom) {
+        let last = t.rows() - 1;
+        if let Some((row, base, given)) = *room {
+            if row == last && given == base + fh && t.row_height(row) == given {
+                return;
+            }
+            if row < t.rows() && t.row_height(row) == given {
+                t.set_row_height(row, base);
+            }
+            *room = None;
+        }
+        if fh > 0 && last >= 0 {
+            let base = t.row_height(last);
+            t.set_row_height(last, base + fh);
+            *room = Some((last, base, base + fh));
+        }
+    }
+
+    // Paints the footer over the bottom of the cells area x, y, w, h, right under the last row
+    // while the rows don't fill the area
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn draw_footer(
+        t: &table::TableRow,
+        data: &CellMatrix,
+        model: Option<&dyn TableModel>,
+        col_props: &[ColProps],
+        filter: &Option<filter::RowFilter>,
+        opts: &TableOpts,
+        cache: &mut FooterCache,
+        (x, y, w, h): (i32, i32, i32, i32),
+    ) {
+        let fh = Self::footer_height(t, col_props);
+        let rows = t.rows();
+        if fh == 0 || rows == 0 {
+            return;
+        }
+        let Some((_, ry, _, rh)) = t.find_cell(TableContext::Cell, rows - 1, 0) else {
+            return;
+        };
+        let fy = (ry + rh - fh).min(y + h - fh);
+        let ncols = t.cols() - opts.rtl as i32;
+        let texts = cache.get_or_insert_with(|| {
+            (0..col_props.len() as i32)
+                .map(|col| {
+                    let agg = &col_props[col as usize].aggregate;
+                    Self::aggregate_col(agg, rows, col, data, model, filter)
+                })
+                .collect()
+        });
+        let (_, _, c1, c2) = t.visible_cells();
+        draw::push_clip(x, y, w, h);
+        for c in c1..=c2 {
+            let Some((cx, _, cw, _)) = t.find_cell(TableContext::Cell, rows - 1, c) else {
+                continue;
+            };
+            match Self::data_col(opts.rtl, ncols, c) {
+                Some(col) => {
+                    let txt = texts.get(col as usize).map_or("", String::as_str);
+                    Self::draw_header(txt, cx, fy, cw, fh, opts);
+                }
+                // Row titles of the right-to-left layout
+                None => Self::draw_header("", cx, fy, cw, fh, &opts.row_headers()),
+            }
+        }
+        draw::pop_clip();
+        if let Some((hx, _, hw, _)) = t
+            .row_header()
+            .then(|| t.find_cell(TableContext::RowHeader, rows - 1, 0))
+            .flatten()
+        {
+            Self::draw_header("", hx, fy, hw, fh, &opts.row_headers());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vals(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn aggregates() {
+        let v = vals(&["1", " 2.5 ", "x", ""]);
+        assert_eq!(Aggregate::None.apply(&v), "");
+        assert_eq!(Aggregate::Sum.apply(&v), "3.5");
+        assert_eq!(Aggregate::Avg.apply(&v), "1.75");
+        assert_eq!(Aggregate::Min.apply(&v), "1");
+        assert_eq!(Aggregate::Max.apply(&v), "2.5");
+        assert_eq!(Aggregate::Count.apply(&v), "3");
+    }
+
+    #[test]
+    fn aggregates_without_numbers() {
+        let v = vals(&["a", ""]);
+        assert_eq!(Aggregate::Sum.apply(&v), "0");
+        assert_eq!(Aggregate::Avg.apply(&v), "");
+        assert_eq!(Aggregate::Min.apply(&v), "");
+        assert_eq!(Aggregate::Max.apply(&[]), "");
+    }
+
+    #[test]
+    fn custom_aggregate() {
+        let join = Aggregate::custom(|v| v.join("+"));
+        assert_eq!(join.apply(&vals(&["a", "b"])), "a+b");
+    }
+
+    #[test]
+    fn number_format() {
+        assert_eq!(format_num(3.0), "3");
+        assert_eq!(format_num(100.0), "100");
+        assert_eq!(format_num(0.0), "0");
+        assert_eq!(format_num(2.5), "2.5");
+        assert_eq!(format_num(1.0 / 3.0), "0.3333");
+        assert_eq!(format_num(-1.23456), "-1.2346");
+        assert_eq!(format_num(0.3 - 0.1 - 0.2), "0");
+        let v = vals(&["0.3", "-0.1", "-0.2"]);
+        assert_eq!(Aggregate::Sum.apply(&v), "0");
+    }
+}
@@ -0,0 +1,103 @@
+// Frozen rows, painted over the top of the cells so they stay visible while scrolling
+
+use super::SmartTable;
+use fltk::{
+    app, draw,
+    enums::*,
+    prelude::{TableExt, ValuatorExt, WidgetExt},
+    table::{self, TableContext},
+};
+
+impl SmartTable {
+    /// Keep the first `n` rows shown at the top of the cells while scrolling vertically,
+    /// e.g. for a units row or a summary. 0 unfreezes them
+    pub fn set_frozen_rows(&mut self, n: i32) {
+        *self.frozen_rows.try_lock().unwrap() = n.max(0);
+        self.table.redraw();
+    }
+
+    /// Returns the number of frozen rows
+    pub fn frozen_rows(&self) -> i32 {
+        *self.frozen_rows.try_lock().unwrap()
+    }
+
+    // Height of the frozen rows, 0 when the view isn't scrolled so they show in place
+    fn frozen_height(t: &table::TableRow, n: i32) -> i32 {
+        if n == 0 || t.scrollbar().value() <= 0.0 {
+            return 0;
+        }
+        (0..n.min(t.rows())).map(|r| t.row_height(r)).sum()
+    }
+
+    // Paints the frozen rows over the cells area x, y, w, h with the table's cell drawing
+    pub(crate) fn draw_frozen_rows<F>(
+        t: &mut table::TableRow,
+        draw_cell: &mut F,
+        n: i32,
+        (x, y, w, h): (i32, i32, i32, i32),
+    ) where
+        F: FnMut(&mut table::TableRow, TableContext, i32, i32, i32, i32, i32, i32),
+    {
+        let band = Self::frozen_height(t, n);
+        if band == 0 {
+            return;
+        }
+        let (_, _, c1, c2) = t.visible_cells();
+        let header = t
+            .row_header()
+            .then(|| t.find_cell(TableContext::RowHeader, 0, 0))
+            .flatten();
+        let mut ry = y;
+        for r in 0..n.min(t.rows()) {
+            let rh = t.row_height(r);
+            // The cells are clipped to the cells area, the nested clips intersect
+            draw::push_clip(x, y, w, h);
+            for c in c1..=c2 {
+                if let Some((cx, _, cw, _)) = t.find_cell(TableContext::Cell, r, c) {
+                    draw::push_clip(cx, ry, cw, rh);
+                    draw_cell(t, TableContext::Cell, r, c, cx, ry, cw, rh);
+                    draw::pop_clip();
+                }
+            }
+            draw::pop_clip();
+            if let Some((hx, _, hw, _)) = header {
+                draw::push_clip(hx, ry, hw, rh);
+                draw_cell(t, TableContext::RowHeader, r, 0, hx, ry, hw, rh);
+                draw::pop_clip();
+            }
+            ry += rh;
+        }
+        // Separates the frozen rows from the scrolling ones
+        draw::push_clip(x, y, w, h);
+        draw::set_draw_color(Color::Dark3);
+        draw::draw_xyline(x, y + band - 1, x + w);
+        draw::pop_clip();
+    }
+
+    // Selects the frozen cell under a click instead of the row scrolled below it
+    pub(crate) fn handle_frozen_click(t: &mut table::TableRow, ev: Event, n: i32) -> bool {
+        if ev != Event::Push {
+            return false;
+        }
+        let band = Self::frozen_height(t, n);
+        let (x0, y0, x1, _) = Self::cells_area(t);
+        let (ex, ey) = app::event_coords();
+        if band == 0 || ex < x0 || ex >= x1 || ey < y0 || ey >= y0 + band {
+            return false;
+        }
+        let Some((TableContext::Cell, _, col, _)) = t.cursor2rowcol() else {
+            return false;
+        };
+        let mut top = y0;
+        let Some(row) = (0..n.min(t.rows())).find(|r| {
+            top += t.row_height(*r);
+            ey < top
+        }) else {
+            return false;
+        };
+        t.set_selection(row, col, row, col);
+        t.take_focus().ok();
+        t.redraw();
+        true
+    }
+}
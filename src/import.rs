@@ -0,0 +1,145 @@
+// The import wizard, a dialog for picking how a delimited file is parsed before loading it
+
+use super::csv::parse;
+use super::{CsvOpts, SmartTable, TableOpts, TextEncoding};
+use fltk::{app, button, menu, prelude::*, window};
+use std::cell::RefCell;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+
+const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+const QUOTES: [char; 2] = ['"', '\''];
+const ENCODINGS: [TextEncoding; 3] = [
+    TextEncoding::Utf8,
+    TextEncoding::Latin1,
+    TextEncoding::Windows1252,
+];
+
+/// A dialog previewing the first lines of a file, letting the user pick the delimiter,
+/// quote character, encoding and whether the first row is a header before loading it
+#[derive(Debug)]
+pub struct ImportWizard {
+    win: window::Window,
+    bytes: Rc<Vec<u8>>,
+    opts: Rc<RefCell<CsvOpts>>,
+    accepted: Rc<RefCell<bool>>,
+}
+
+impl ImportWizard {
+    /// Creates the wizard for the file, previewing its first `preview_lines` lines
+    pub fn new<P: AsRef<Path>>(path: P, preview_lines: usize) -> io::Result<Self> {
+        let bytes = Rc::new(std::fs::read(path)?);
+        let sample: Vec<u8> = bytes
+            .split_inclusive(|b| *b == b'\n')
+            .take(preview_lines)
+            .flatten()
+            .copied()
+            .collect();
+        let opts = Rc::new(RefCell::new(CsvOpts::default()));
+        let accepted = Rc::new(RefCell::new(false));
+
+        let mut win = window::Window::default()
+            .with_size(600, 400)
+            .with_label("Import");
+        let mut delim = menu::Choice::new(80, 10, 110, 25, "Delimiter");
+        delim.add_choice("Comma|Semicolon|Tab|Pipe");
+        delim.set_value(0);
+        let mut quote = menu::Choice::new(270, 10, 60, 25, "Quote");
+        quote.add_choice("\"|'");
+        quote.set_value(0);
+        let mut enc = menu::Choice::new(420, 10, 170, 25, "Encoding");
+        enc.add_choice("UTF-8|Latin-1|Windows-1252");
+        enc.set_value(0);
+        let mut header = button::CheckButton::new(10, 45, 200, 25, "First row is header");
+        let mut preview = SmartTable::new(10, 80, 580, 270, None);
+        preview.set_opts(TableOpts {
+            rows: 0,
+            cols: 0,
+            editable: false,
+            ..Default::default()
+        });
+        let mut ok = button::ReturnButton::new(410, 360, 85, 30, "Import");
+        let mut cancel = button::Button::new(505, 360, 85, 30, "Cancel");
+        win.end();
+        win.make_modal(true);
+
+        // Reparses the sample with the picked options
+        let refresh = Rc::new({
+            let (delim, quote, enc, header) =
+                (delim.clone(), quote.clone(), enc.clone(), header.clone());
+            let opts = opts.clone();
+            move || {
+                let mut o = opts.borrow_mut();
+                o.delimiter = DELIMITERS[delim.value().max(0) as usize];
+                o.quote = QUOTES[quote.value().max(0) as usize];
+                o.encoding = ENCODINGS[enc.value().max(0) as usize];
+                o.has_header = header.is_checked();
+                let mut preview = preview.clone();
+                let cols = preview.column_count();
+                for c in 0..cols {
+                    preview.set_col_header_value(c, &SmartTable::default_col_header(c));
+                }
+                preview.load_records(parse(&o.encoding.decode(&sample), &o), o.has_header);
+            }
+        });
+        refresh();
+
+        delim.set_callback({
+            let refresh = refresh.clone();
+            move |_| refresh()
+        });
+        quote.set_callback({
+            let refresh = refresh.clone();
+            move |_| refresh()
+        });
+        enc.set_callback({
+            let refresh = refresh.clone();
+            move |_| refresh()
+        });
+        header.set_callback({
+            let refresh = refresh.clone();
+            move |_| refresh()
+        });
+        ok.set_callback({
+            let accepted = accepted.clone();
+            move |b| {
+                *accepted.borrow_mut() = true;
+                if let Some(mut win) = b.window() {
+                    win.hide();
+                }
+            }
+        });
+        cancel.set_callback(|b| {
+            if let Some(mut win) = b.window() {
+                win.hide();
+            }
+        });
+
+        Ok(Self {
+            win,
+            bytes,
+            opts,
+            accepted,
+        })
+    }
+
+    /// Shows the wizard and waits for it to close. If the user accepted, the whole file is
+    /// loaded into the table and the chosen options are returned, otherwise `None`
+    pub fn run(&mut self, table: &mut SmartTable) -> Option<CsvOpts> {
+        *self.accepted.borrow_mut() = false;
+        self.win.show();
+        while self.win.shown() {
+            app::wait();
+        }
+        if !*self.accepted.borrow() {
+            return None;
+        }
+        let opts = *self.opts.borrow();
+        table.load_records(
+            parse(&opts.encoding.decode(&self.bytes), &opts),
+            opts.has_header,
+        );
+        Some(opts)
+    }
+}
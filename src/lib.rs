@@ -1,16 +1,89 @@
 #![doc = include_str!("../README.md")]
 #![allow(clippy::needless_doctest_main)]
 
+mod autosize;
+mod batch;
+mod celldraw;
+mod chooser;
+#[cfg(feature = "editing")]
+mod complete;
+mod csv;
+mod dropdown;
+#[cfg(feature = "editing")]
+mod editing;
+mod error;
+mod filter;
+mod filterrow;
+mod find;
+mod footer;
+mod frozen;
+mod import;
+mod links;
+#[cfg(feature = "editing")]
+mod mask;
+mod model;
+mod pager;
+mod pattern;
+#[cfg(feature = "perf")]
+mod perf;
+mod print;
+#[cfg(feature = "editing")]
+mod rename;
+mod sort;
+mod tooltip;
+mod tree;
+mod undo;
+mod value;
+mod workbook;
+mod wrap;
+
+#[cfg(feature = "editing")]
+use fltk::input;
 use fltk::{
-    app, draw,
+    app,
+    draw::{self, LineStyle},
     enums::*,
-    input,
-    prelude::{GroupExt, InputExt, TableExt, WidgetBase, WidgetExt},
-    table, window,
+    prelude::{GroupExt, ImageExt, TableExt, ValuatorExt, WidgetBase, WidgetExt},
+    table,
 };
+use std::any::Any;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub use batch::TableBatch;
+pub use celldraw::DrawCtx;
+#[cfg(feature = "editing")]
+pub use complete::Completion;
+pub use csv::{CsvOpts, CsvQuoting, TextEncoding};
+#[cfg(feature = "editing")]
+pub use editing::CellEditor;
+pub use error::TableError;
+pub use find::FindOpts;
+pub use footer::Aggregate;
+pub use import::ImportWizard;
+pub use model::TableModel;
+#[cfg(feature = "perf")]
+pub use perf::PerfStats;
+pub use print::{PrintOpts, PrintPage, PrintPreview};
+pub use sort::SortOrder;
+pub use value::CellValue;
+pub use workbook::TableWorkbook;
+
+/// Overrides of the table's styling for one cell, see `SmartTable::set_cell_style()`.
+/// Fields left to `None` use the table's styling
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CellStyle {
+    pub color: Option<Color>,
+    pub font: Option<Font>,
+    pub font_color: Option<Color>,
+    pub font_size: Option<i32>,
+    pub align: Option<Align>,
+    pub selection_color: Option<Color>,
+    pub border_color: Option<Color>,
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct Cell {
@@ -22,19 +95,191 @@ pub struct Cell {
     selection_color: Option<Color>,
     align: Option<Align>,
     border_color: Option<Color>,
+    tooltip: Option<String>,
+    link: bool,
+    locked: bool,
+    // Typed value the label was made from, None for plain text
+    value: Option<CellValue>,
 }
 
 impl Cell {
     fn with_label(l: &str) -> Cell {
+        Self::from_string(l.to_string())
+    }
+
+    fn from_string(label: String) -> Cell {
         Cell {
-            label: l.to_string(),
+            label,
             ..Default::default()
         }
     }
+
+    /// The cell's text
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The cell's typed value, plain text gives `CellValue::Str`, or `CellValue::Empty` when empty
+    pub fn value(&self) -> CellValue {
+        match &self.value {
+            Some(v) => v.clone(),
+            None if self.label.is_empty() => CellValue::Empty,
+            None => CellValue::Str(self.label.clone()),
+        }
+    }
+
+    // Typed numbers as is, text parsed
+    fn as_f64(&self) -> Option<f64> {
+        match &self.value {
+            Some(v) => v.as_f64(),
+            None => self.label.trim().parse().ok(),
+        }
+    }
+
+    fn set_value(&mut self, value: CellValue) {
+        self.label = value.to_string();
+        self.value = match value {
+            CellValue::Str(_) | CellValue::Empty => None,
+            v => Some(v),
+        };
+    }
+
+    // Sets the text, a typed cell keeps its type if the text parses as one
+    fn set_text(&mut self, txt: String) {
+        self.value = self.value.as_ref().and_then(|v| v.parse_like(&txt));
+        self.label = txt;
+    }
 }
 
 type CellMatrix = Vec<Vec<Cell>>;
 
+/// A matrix of cell values, indexed by row then column
+pub type StringMatrix = Vec<Vec<String>>;
+
+// Per-column properties, kept in step with the columns like the column headers
+#[derive(Debug, Default, Clone)]
+struct ColProps {
+    align: Option<Align>,
+    rtl: Option<bool>,
+    tooltip: Option<String>,
+    width: Option<ColWidth>,
+    // Width to restore when the column is shown again, set while it's hidden
+    hidden: Option<i32>,
+    read_only: bool,
+    wrap: bool,
+    #[cfg(feature = "editing")]
+    editor: CellEditor,
+    #[cfg(feature = "editing")]
+    completion: Completion,
+    aggregate: Aggregate,
+}
+
+/// How a column's width is set, see `SmartTable::set_col_width_mode()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColWidth {
+    /// A width in pixels
+    Fixed(i32),
+    /// A percentage of the width available to the columns
+    Percent(f32),
+    /// Share the width left over by the other columns with the other stretching columns
+    Stretch,
+}
+
+/// How the columns fill the width of the table, see `TableOpts::col_layout`.
+/// Columns with a width mode keep it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColLayout {
+    /// The columns keep their widths
+    #[default]
+    Fixed,
+    /// The last visible column takes the width left over by the others
+    StretchLast,
+    /// The columns are scaled together to fill the width whenever the table is resized
+    Proportional,
+}
+
+/// Initial configuration of a column, see `SmartTable::set_col_opts()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColOpts {
+    /// Width of the column, the table's default when `None`
+    pub width: Option<ColWidth>,
+    /// Alignment of the column's cells, `cell_align` when `None`
+    pub align: Option<Align>,
+    /// Whether the column's cells can be edited in an editable table
+    pub editable: bool,
+    /// Wrap the text onto multiple lines, growing the rows to fit
+    pub wrap: bool,
+}
+
+impl ColOpts {
+    /// A column with the table's defaults
+    pub const fn new() -> Self {
+        Self {
+            width: None,
+            align: None,
+            editable: true,
+            wrap: false,
+        }
+    }
+
+    /// Set the width
+    pub const fn width(mut self, width: ColWidth) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Set the alignment
+    pub const fn align(mut self, align: Align) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    /// Set whether the column can be edited
+    pub const fn editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+
+    /// Set whether the text wraps
+    pub const fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+}
+
+impl Default for ColOpts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type AnnounceCallback = Box<dyn FnMut(&str) + Send>;
+type UpdateCallback = Box<dyn FnMut(i32, i32, String) + Send>;
+#[cfg(feature = "editing")]
+type Validator = Box<dyn FnMut(i32, i32, &str) -> Result<(), String> + Send>;
+type SelectionCallback = Box<dyn FnMut(&mut SmartTable) + Send>;
+type CellChangeCallback = Box<dyn FnMut(&mut SmartTable, i32, i32, &str, &str) + Send>;
+type LimitCallback = Box<dyn FnMut(TableLimit) + Send>;
+type ColWatcher = (Vec<i32>, Box<dyn FnMut(i32, i32, &str) + Send>);
+type DebouncedFn = (Duration, Arc<Mutex<dyn FnMut(&mut SmartTable) + Send>>);
+type ScrollCallback = Box<dyn FnMut(&mut SmartTable) + Send>;
+type NeedRowsCallback = Box<dyn FnMut(&mut SmartTable, i32) + Send>;
+type Model = Box<dyn TableModel>;
+type OverlayFn = Box<dyn FnMut(&SmartTable, (i32, i32, i32, i32)) + Send>;
+type RowBandFn = Box<dyn Fn(i32, &[Cell]) -> Option<Color> + Send>;
+type ReadOnlyFn = Box<dyn Fn(i32, i32) -> bool + Send>;
+type HeaderLabelFn = Box<dyn Fn(i32) -> String + Send>;
+
+// How rows get their band colors
+enum RowBand {
+    Fn(RowBandFn),
+    // Alternates between the two colors whenever the value in the column changes
+    Col(i32, [Color; 2]),
+}
+
+/// An arbitrary value attached to a row, see [`SmartTable::set_row_tag`]
+pub type RowTag = Arc<dyn Any + Send + Sync>;
+
 // Needed to store cell information during the draw_cell call
 #[derive(Default)]
 struct CellData {
@@ -57,11 +302,94 @@ impl CellData {
     }
 }
 
+// State of a drag-selection, which keeps scrolling while the pointer is past the cells
+#[derive(Default)]
+struct DragState {
+    anchor: Option<(i32, i32)>,
+    timer: Option<app::TimeoutHandle>,
+}
+
+// State of a touch pan and its momentum
+#[derive(Default)]
+struct TouchState {
+    last: Option<(i32, i32, std::time::Instant)>,
+    travel: i32,
+    velocity: (f64, f64),
+    timer: Option<app::TimeoutHandle>,
+}
+
+/// What a click selects, see `TableOpts::selection_mode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// Cells and ranges of cells
+    #[default]
+    Cell,
+    /// A single whole row, like a Browser widget
+    Row,
+    /// Whole rows, Ctrl and Shift clicks add to the selection
+    MultiRow,
+}
+
+/// What opens the inline editor of a cell, see `TableOpts::edit_trigger`.
+/// Enter and F2 open it whatever the trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EditTrigger {
+    /// Clicking the cell
+    #[default]
+    SingleClick,
+    /// Double-clicking the cell, a single click only selects it
+    DoubleClick,
+    /// Only Enter and F2, or typing when `TableOpts::type_to_edit` is set
+    EnterKey,
+}
+
+/// What the inline editor does when it loses the focus, see `TableOpts::edit_focus_loss`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FocusLossPolicy {
+    /// Store the edited value, as if Enter was pressed
+    #[default]
+    Commit,
+    /// Discard the edited value, as if Escape was pressed
+    Cancel,
+}
+
+/// What happens to appends past the limits set with `SmartTable::set_limits()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Remove the oldest rows, or the leftmost columns, to make room like a ring buffer
+    #[default]
+    DropOldest,
+    /// Ignore the whole append, e.g. all the rows of `extend_rows()` when some don't fit
+    Reject,
+}
+
+/// The limit hit by an append, passed to the `on_limit_reached` callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableLimit {
+    Rows,
+    Cols,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Limits {
+    max_rows: Option<usize>,
+    max_cols: Option<usize>,
+    policy: OverflowPolicy,
+}
+
+// Escapes the characters FLTK menus interpret in item labels
+fn menu_label(item: &str) -> String {
+    item.replace('\\', "\\\\")
+        .replace('/', "\\/")
+        .replace('&', "&&")
+}
+
 /// Contains the parameters for our table, including rows, columns and other styling params
 #[derive(Debug, Clone, Copy)]
 pub struct TableOpts {
     pub rows: i32,
     pub cols: i32,
+    /// Whether cells can be edited inline, this has no effect without the `editing` feature
     pub editable: bool,
     pub cell_color: Color,
     pub cell_font: Font,
@@ -71,12 +399,134 @@ pub struct TableOpts {
     pub cell_align: Align,
     pub cell_border_color: Color,
     pub cell_padding: i32,
+    /// Thickness of the cell borders
+    pub cell_border_width: i32,
     pub header_font: Font,
     pub header_frame: FrameType,
     pub header_color: Color,
     pub header_font_color: Color,
     pub header_font_size: i32,
     pub header_align: Align,
+    /// Color of the outline drawn around the selected range
+    pub selection_border_color: Color,
+    /// Width of the outline drawn around the selected range, 0 disables it
+    pub selection_border_width: i32,
+    /// Line style of the focus rectangle drawn in the focused cell, `None` disables it
+    pub focus_line_style: Option<LineStyle>,
+    /// Color of the focus rectangle
+    pub focus_color: Color,
+    /// Background tint for the unselected cells sharing a row with the focused cell
+    pub row_tint: Option<Color>,
+    /// Scale paddings, borders, resize grab zones and the editor by the screen's DPI,
+    /// on top of the scaling FLTK already applies
+    pub dpi_aware: bool,
+    /// Right-to-left layout: columns are mirrored so the first column is rightmost,
+    /// row headers are drawn to the right of it, and text is drawn right to left
+    pub rtl: bool,
+    /// Pixels by which Ctrl+Left/Right resizes the focused column and Ctrl+Up/Down its row,
+    /// 0 disables keyboard resizing
+    pub resize_step: i32,
+    /// Scroll the mouse wheel by pixels instead of jumping by whole rows and columns
+    pub smooth_scroll: bool,
+    /// Dragging on the cells pans the view with momentum instead of selecting, for touch screens.
+    /// A tap still selects the cell
+    pub touch_scroll: bool,
+    /// Ignore the cell, header, border and focus colors and follow the app's current palette
+    /// instead, so the table matches light and dark themes and picks up theme changes on redraw
+    pub theme_colors: bool,
+    /// Number of cell edits which can be undone, 0 disables undo
+    pub undo_depth: usize,
+    /// Whether clicks select cells or whole rows
+    pub selection_mode: SelectionMode,
+    /// Typing over the focused cell opens its editor, replacing the content with the typed text
+    pub type_to_edit: bool,
+    /// Delete and Backspace clear the selected cells of editable tables
+    pub delete_clears: bool,
+    /// Background of every other row, for striping
+    pub alt_row_color: Option<Color>,
+    /// Right-clicking the column headers pops up a menu toggling the columns' visibility
+    pub col_chooser: bool,
+    /// How the columns fill the width of the table
+    pub col_layout: ColLayout,
+    /// Text color of the link cells
+    pub link_color: Color,
+    /// Double-clicking a column header opens an editor renaming it,
+    /// this has no effect without the `editing` feature
+    pub rename_headers: bool,
+    /// Show the row headers, 1, 2, 3... by default
+    pub show_row_headers: bool,
+    /// Show the column headers, A, B, C... by default
+    pub show_col_headers: bool,
+    /// Frame of the row headers, `header_frame` when None
+    pub row_header_frame: Option<FrameType>,
+    /// Background of the row headers, `header_color` when None
+    pub row_header_color: Option<Color>,
+    /// Font of the row headers, `header_font` when None
+    pub row_header_font: Option<Font>,
+    /// Text color of the row headers, `header_font_color` when None
+    pub row_header_font_color: Option<Color>,
+    /// Font size of the row headers, `header_font_size` when None
+    pub row_header_font_size: Option<i32>,
+    /// Alignment of the row headers, `header_align` when None
+    pub row_header_align: Option<Align>,
+    /// What the inline editor does when the focus moves to another widget,
+    /// or its cell is scrolled out of view
+    pub edit_focus_loss: FocusLossPolicy,
+    /// What opens the inline editor of a cell
+    pub edit_trigger: EditTrigger,
+    /// Background of the cells matched by `SmartTable::find()`
+    pub find_color: Color,
+    /// Keep the last row in view as rows are appended, unless the view was scrolled up
+    /// from the bottom, like a terminal. For log tables
+    pub follow_tail: bool,
+    /// Draw an arrow at the right of the column headers popping up a menu of the column's
+    /// values, unchecking a value hides its rows
+    pub filter_dropdowns: bool,
+}
+
+impl TableOpts {
+    // A copy with the paddings and border widths multiplied by the scale factor
+    fn scaled(&self, factor: f32) -> TableOpts {
+        let scale = |v: i32| (v as f32 * factor).round() as i32;
+        TableOpts {
+            cell_padding: scale(self.cell_padding),
+            cell_border_width: scale(self.cell_border_width),
+            selection_border_width: scale(self.selection_border_width),
+            ..*self
+        }
+    }
+
+    // A copy with the header styling replaced by the row headers' one
+    fn row_headers(&self) -> TableOpts {
+        TableOpts {
+            header_frame: self.row_header_frame.unwrap_or(self.header_frame),
+            header_color: self.row_header_color.unwrap_or(self.header_color),
+            header_font: self.row_header_font.unwrap_or(self.header_font),
+            header_font_color: self.row_header_font_color.unwrap_or(self.header_font_color),
+            header_font_size: self.row_header_font_size.unwrap_or(self.header_font_size),
+            header_align: self.row_header_align.unwrap_or(self.header_align),
+            ..*self
+        }
+    }
+
+    // Colors derived from the app's palette, the indexed colors resolve to the current theme
+    fn themed(&self) -> TableOpts {
+        let fg = Color::Foreground;
+        let bg = Color::BackGround2;
+        TableOpts {
+            cell_color: bg,
+            cell_font_color: fg,
+            cell_selection_color: Color::color_average(Color::Selection, bg, 0.4),
+            cell_border_color: Color::color_average(fg, bg, 0.25),
+            header_color: Color::Background,
+            header_font_color: Color::contrast(fg, Color::Background),
+            row_header_color: None,
+            row_header_font_color: None,
+            selection_border_color: Color::Selection,
+            focus_color: fg,
+            ..*self
+        }
+    }
 }
 
 impl Default for TableOpts {
@@ -93,12 +543,46 @@ impl Default for TableOpts {
             cell_align: Align::Center,
             cell_border_color: Color::Gray0,
             cell_padding: 1,
+            cell_border_width: 1,
             header_font: Font::Helvetica,
             header_frame: FrameType::ThinUpBox,
             header_color: Color::FrameDefault,
             header_font_color: Color::Black,
             header_font_size: 14,
             header_align: Align::Center,
+            selection_border_color: Color::Selection,
+            selection_border_width: 0,
+            focus_line_style: None,
+            focus_color: Color::Gray0,
+            row_tint: None,
+            dpi_aware: false,
+            rtl: false,
+            resize_step: 5,
+            smooth_scroll: false,
+            touch_scroll: false,
+            theme_colors: false,
+            undo_depth: 100,
+            selection_mode: SelectionMode::Cell,
+            type_to_edit: false,
+            delete_clears: true,
+            alt_row_color: None,
+            col_chooser: false,
+            col_layout: ColLayout::Fixed,
+            link_color: Color::Blue,
+            rename_headers: false,
+            show_row_headers: true,
+            show_col_headers: true,
+            row_header_frame: None,
+            row_header_color: None,
+            row_header_font: None,
+            row_header_font_color: None,
+            row_header_font_size: None,
+            row_header_align: None,
+            edit_focus_loss: FocusLossPolicy::Commit,
+            edit_trigger: EditTrigger::SingleClick,
+            find_color: Color::from_rgb(255, 236, 128),
+            follow_tail: false,
+            filter_dropdowns: false,
         }
     }
 }
@@ -107,11 +591,64 @@ impl Default for TableOpts {
 #[derive(Clone)]
 pub struct SmartTable {
     table: table::TableRow,
+    #[cfg(feature = "editing")]
     inp: Option<input::Input>,
+    #[cfg(feature = "editing")]
+    choice: Option<fltk::menu::Choice>,
+    #[cfg(feature = "editing")]
+    text_editor: Option<fltk::text::TextEditor>,
+    #[cfg(feature = "editing")]
+    completions: Option<fltk::browser::HoldBrowser>,
+    #[cfg(feature = "editing")]
+    header_inp: Option<input::Input>,
     data: Arc<Mutex<CellMatrix>>,
     row_headers: Arc<Mutex<Vec<String>>>,
     col_headers: Arc<Mutex<Vec<String>>>,
-    on_update_callback: Arc<Mutex<Box<dyn FnMut(i32, i32, String) + Send>>>,
+    row_tags: Arc<Mutex<Vec<Option<RowTag>>>>,
+    tree: Arc<Mutex<tree::TreeNodes>>,
+    col_props: Arc<Mutex<Vec<ColProps>>>,
+    opts: Arc<Mutex<TableOpts>>,
+    on_update_callback: Arc<Mutex<UpdateCallback>>,
+    on_cell_change: Arc<Mutex<Option<CellChangeCallback>>>,
+    on_selection_changed: Arc<Mutex<Option<SelectionCallback>>>,
+    #[cfg(feature = "editing")]
+    validator: Arc<Mutex<Option<Validator>>>,
+    #[cfg(feature = "editing")]
+    edit_error: Arc<Mutex<Option<String>>>,
+    #[cfg(feature = "editing")]
+    on_header_renamed: Arc<Mutex<Option<rename::HeaderRenamedCallback>>>,
+    announce_callback: Arc<Mutex<AnnounceCallback>>,
+    overlay: Arc<Mutex<Option<OverlayFn>>>,
+    cell_draw: Arc<Mutex<Option<celldraw::CellDrawFn>>>,
+    model: Arc<Mutex<Option<Model>>>,
+    #[cfg(feature = "perf")]
+    perf: Arc<Mutex<PerfStats>>,
+    col_watchers: Arc<Mutex<Vec<ColWatcher>>>,
+    limits: Arc<Mutex<Limits>>,
+    readonly_predicate: Arc<Mutex<Option<ReadOnlyFn>>>,
+    col_opts: Arc<Mutex<Vec<ColOpts>>>,
+    row_header_label: Arc<Mutex<Option<HeaderLabelFn>>>,
+    col_header_label: Arc<Mutex<Option<HeaderLabelFn>>>,
+    found: Arc<Mutex<find::FindState>>,
+    changed: Arc<Mutex<Option<Instant>>>,
+    footer: Arc<Mutex<footer::FooterCache>>,
+    on_changed_debounced: Arc<Mutex<Option<DebouncedFn>>>,
+    on_scroll: Arc<Mutex<Option<ScrollCallback>>>,
+    // Rows from the bottom asking for more, and the callback
+    on_need_more_rows: Arc<Mutex<Option<(i32, NeedRowsCallback)>>>,
+    on_limit_reached: Arc<Mutex<Option<LimitCallback>>>,
+    watermark: Arc<Mutex<Option<fltk::image::RgbImage>>>,
+    row_band: Arc<Mutex<Option<RowBand>>>,
+    frozen_rows: Arc<Mutex<i32>>,
+    tooltips: Arc<Mutex<tooltip::Tooltips>>,
+    on_link_click: Arc<Mutex<Option<links::LinkCallback>>>,
+    on_row_expanded: Arc<Mutex<Option<tree::ExpandCallback>>>,
+    sort: Arc<Mutex<sort::SortState>>,
+    filter: Arc<Mutex<Option<filter::RowFilter>>>,
+    filter_row: Arc<Mutex<Option<filterrow::FilterRow>>>,
+    on_filter_changed: Arc<Mutex<Option<filterrow::FilterCallback>>>,
+    pager: Arc<Mutex<Option<fltk::frame::Frame>>>,
+    undo_stack: Arc<Mutex<undo::UndoStack>>,
 }
 
 impl Default for SmartTable {
@@ -125,17 +662,69 @@ impl SmartTable {
     pub fn new<S: Into<Option<&'static str>>>(x: i32, y: i32, w: i32, h: i32, label: S) -> Self {
         let table = table::TableRow::new(x, y, w, h, label);
         table.end();
-        let inp = None;
-        let on_update_callback: Box<dyn FnMut(i32, i32, String) + Send> = Box::new(|_, _, _| ());
+        let on_update_callback: UpdateCallback = Box::new(|_, _, _| ());
         let on_update_callback = Arc::new(Mutex::new(on_update_callback));
+        let announce_callback: AnnounceCallback = Box::new(|_| ());
 
         Self {
             table,
-            inp,
+            #[cfg(feature = "editing")]
+            inp: None,
+            #[cfg(feature = "editing")]
+            choice: None,
+            #[cfg(feature = "editing")]
+            text_editor: None,
+            #[cfg(feature = "editing")]
+            completions: None,
+            #[cfg(feature = "editing")]
+            header_inp: None,
             data: Default::default(),
             row_headers: Default::default(),
             col_headers: Default::default(),
+            row_tags: Default::default(),
+            tree: Default::default(),
+            col_props: Default::default(),
+            opts: Default::default(),
             on_update_callback,
+            on_cell_change: Default::default(),
+            on_selection_changed: Default::default(),
+            #[cfg(feature = "editing")]
+            validator: Default::default(),
+            #[cfg(feature = "editing")]
+            edit_error: Default::default(),
+            #[cfg(feature = "editing")]
+            on_header_renamed: Default::default(),
+            announce_callback: Arc::new(Mutex::new(announce_callback)),
+            overlay: Default::default(),
+            cell_draw: Default::default(),
+            model: Default::default(),
+            #[cfg(feature = "perf")]
+            perf: Default::default(),
+            col_watchers: Default::default(),
+            limits: Default::default(),
+            readonly_predicate: Default::default(),
+            col_opts: Default::default(),
+            row_header_label: Default::default(),
+            col_header_label: Default::default(),
+            found: Default::default(),
+            changed: Default::default(),
+            footer: Default::default(),
+            on_changed_debounced: Default::default(),
+            on_scroll: Default::default(),
+            on_need_more_rows: Default::default(),
+            on_limit_reached: Default::default(),
+            sort: Default::default(),
+            filter: Default::default(),
+            filter_row: Default::default(),
+            on_filter_changed: Default::default(),
+            pager: Default::default(),
+            undo_stack: Default::default(),
+            watermark: Default::default(),
+            row_band: Default::default(),
+            frozen_rows: Default::default(),
+            tooltips: Default::default(),
+            on_link_click: Default::default(),
+            on_row_expanded: Default::default(),
         }
     }
 
@@ -146,147 +735,671 @@ impl SmartTable {
             .center_of_parent()
     }
 
-    /// Sets the tables options
+    /// Sets the tables options. The headers are reset, see `set_dimensions()` to only resize
     pub fn set_opts(&mut self, opts: TableOpts) {
+        self.forget_positions();
         let mut data = self.data.try_lock().unwrap();
         data.resize(opts.rows as _, vec![]);
         for v in data.iter_mut() {
             v.resize(opts.cols as _, Cell::default());
         }
         drop(data);
+        self.row_tags
+            .try_lock()
+            .unwrap()
+            .resize(opts.rows as _, None);
+        self.tree.try_lock().unwrap().resize(opts.rows as _, None);
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .resize(opts.cols as _, ColProps::default());
+        self.apply_col_opts();
+        *self.opts.try_lock().unwrap() = opts;
 
-        let mut row_headers = vec![];
-        for i in 0..opts.rows {
-            row_headers.push((i + 1).to_string());
-        }
-        let row_headers = Arc::new(Mutex::new(row_headers));
-        self.row_headers = row_headers;
-
-        let mut col_headers = vec![];
-        for i in 0..opts.cols {
-            let mut pref = String::new();
-            if i > 25 {
-                let t = (i / 26) as i32;
-                if t > 26 {
-                    col_headers.push(i.to_string());
-                } else {
-                    pref.push((t - 1 + 65) as u8 as char);
-                    col_headers.push(format!("{}{}", pref, (i - (26 * t) + 65) as u8 as char));
-                }
-            } else {
-                col_headers.push(format!("{}", (i + 65) as u8 as char));
-            }
-        }
-        let col_headers = Arc::new(Mutex::new(col_headers));
-        self.col_headers = col_headers;
+        // Replaced in place so the clones of the table see the new headers
+        *self.row_headers.try_lock().unwrap() = (0..opts.rows).map(|i| self.row_label(i)).collect();
+        *self.col_headers.try_lock().unwrap() = (0..opts.cols).map(|i| self.col_label(i)).collect();
 
-        let len = opts.rows;
         let inner_len = opts.cols;
 
         let cell = Rc::from(RefCell::from(CellData::default()));
-        self.table.set_rows(len as i32);
-        self.table.set_cols(inner_len as i32);
-        self.table.set_row_header(true);
+        let scale = Rc::from(RefCell::from(1.0f32));
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &self.data.try_lock().unwrap(),
+            &self.footer,
+        );
+        // In right-to-left mode, an extra trailing column holds the row headers
+        self.table.set_cols(inner_len + opts.rtl as i32);
+        Self::clamp_selection(&mut self.table);
         self.table.set_row_resize(true);
-        self.table.set_col_header(true);
+        self.table.set_type(match opts.selection_mode {
+            SelectionMode::Cell | SelectionMode::MultiRow => table::TableRowSelectMode::Multi,
+            SelectionMode::Row => table::TableRowSelectMode::Single,
+        });
         self.table.set_col_resize(true);
+        Self::apply_headers(&mut self.table, &opts);
+        if opts.rtl {
+            self.table.set_col_position(inner_len);
+        }
+        Self::apply_width_modes(&mut self.table, &self.col_props.try_lock().unwrap(), &opts);
         self.table.end();
 
+        #[cfg(feature = "editing")]
+        if opts.editable {
+            self.setup_editor(&cell);
+        }
+        #[cfg(feature = "editing")]
+        if opts.rename_headers {
+            self.setup_header_editor();
+        }
+
         // Called when the table is drawn then when it's redrawn due to events
-        self.table.draw_cell({
+        let mut draw_cell = {
             let cell = cell.clone();
+            let scale = scale.clone();
             let data = self.data.clone();
             let row_headers = self.row_headers.clone();
             let col_headers = self.col_headers.clone();
-            move |t, ctx, row, col, x, y, w, h| {
-                if let Ok(data) = data.try_lock() {
+            let col_props = self.col_props.clone();
+            let row_band = self.row_band.clone();
+            let overlay = self.overlay.clone();
+            let cell_draw = self.cell_draw.clone();
+            let found = self.found.clone();
+            let watermark = self.watermark.clone();
+            let model = self.model.clone();
+            let filter = self.filter.clone();
+            let footer = self.footer.clone();
+            #[cfg(feature = "perf")]
+            let perf = self.perf.clone();
+            let this = self.clone();
+            let mut bands = vec![];
+            let mut grown = wrap::GrownRows::new();
+            let mut footer_room: footer::FooterRoom = None;
+            let mut tree_shown = false;
+            // Height of the filter row at the bottom of the column headers
+            let mut header_band = 0;
+            #[cfg(feature = "editing")]
+            let mut last_geometry = None;
+            let mut last_view = None;
+            let mut last_paging = None;
+            // Row count more rows were last asked for at
+            let mut asked_rows = None;
+            // Rows and whether the view was scrolled to the bottom in the last frame
+            let mut tail: Option<(i32, bool)> = None;
+            let mut base_opts = opts;
+            let mut opts = opts;
+            move |t: &mut table::TableRow,
+                  ctx: table::TableContext,
+                  row: i32,
+                  col: i32,
+                  x: i32,
+                  y: i32,
+                  w: i32,
+                  h: i32| {
+                if let table::TableContext::EndPage = ctx {
+                    // Everything is painted, x, y, w, h is the cells area
+                    if let Some(img) = &mut *watermark.try_lock().unwrap() {
+                        Self::draw_watermark(img, x, y, w, h);
+                    }
+                    if let (Ok(data), Ok(model), Ok(col_props), Ok(filter), Ok(mut footer)) = (
+                        data.try_lock(),
+                        model.try_lock(),
+                        col_props.try_lock(),
+                        filter.try_lock(),
+                        footer.try_lock(),
+                    ) {
+                        Self::draw_footer(
+                            t,
+                            &data,
+                            model.as_deref(),
+                            &col_props,
+                            &filter,
+                            &opts,
+                            &mut footer,
+                            (x, y, w, h),
+                        );
+                    }
+                    if let Some(f) = &mut *overlay.try_lock().unwrap() {
+                        draw::push_clip(x, y, w, h);
+                        f(&this, this.visible_range());
+                        draw::pop_clip();
+                    }
+                    #[cfg(feature = "perf")]
+                    perf.lock().unwrap().frame_end();
+                    return;
+                }
+                #[cfg(feature = "perf")]
+                let lock_start = std::time::Instant::now();
+                let data = data.try_lock();
+                #[cfg(feature = "perf")]
+                perf.lock()
+                    .unwrap()
+                    .record(ctx, data.is_ok(), lock_start.elapsed());
+                if let Ok(data) = data {
                     let row_headers = row_headers.try_lock().unwrap();
                     let col_headers = col_headers.try_lock().unwrap();
+                    let col_props = col_props.try_lock().unwrap();
+                    let model = model.try_lock().unwrap();
+                    let model = model.as_deref();
+                    let view_row = row;
+                    let row = Self::data_row(&filter.try_lock().unwrap(), row);
+                    // The last row's room for the footer is left to it
+                    let h = match (ctx, footer_room) {
+                        (
+                            table::TableContext::Cell | table::TableContext::RowHeader,
+                            Some((r, base, given)),
+                        ) if r == view_row => h - (given - base),
+                        _ => h,
+                    };
+                    let ncols = t.cols() - opts.rtl as i32;
+                    let data_col = Self::data_col(opts.rtl, ncols, col);
+                    let hidden = data_col
+                        .and_then(|c| col_props.get(c as usize))
+                        .is_some_and(|p| p.hidden.is_some());
+                    let row_header = |row: i32| match model {
+                        Some(m) => m.row_header(row),
+                        None => row_headers[row as usize].clone(),
+                    };
                     match ctx {
-                        table::TableContext::StartPage => draw::set_font(Font::Helvetica, 14),
-                        table::TableContext::ColHeader => {
-                            Self::draw_header(&col_headers[col as usize], x, y, w, h, &opts)
-                        } // Column titles
-                        table::TableContext::RowHeader => {
-                            Self::draw_header(&row_headers[row as usize], x, y, w, h, &opts)
-                        } // Row titles
-                        table::TableContext::Cell => {
-                            if t.is_selected(row, col) {
-                                cell.borrow_mut().select(row, col, x, y, w, h); // Captures the cell information
+                        table::TableContext::StartPage => {
+                            // The view scrolled or shows other cells, the callback is called
+                            // once drawing ends
+                            let view = (
+                                (t.scrollbar().value(), t.hscrollbar().value()),
+                                t.visible_cells(),
+                            );
+                            if last_view.replace(view).is_some_and(|v| v != view) {
+                                let mut this = this.clone();
+                                app::add_timeout3(0.0, move |_| {
+                                    let cb = this.on_scroll.clone();
+                                    if let Some(cb) = &mut *cb.try_lock().unwrap() {
+                                        cb(&mut this);
+                                    };
+                                });
+                            }
+                            // Nearing the last row, more rows are asked for once per row count
+                            let near = this
+                                .on_need_more_rows
+                                .try_lock()
+                                .ok()
+                                .and_then(|cb| cb.as_ref().map(|(n, _)| *n));
+                            if let Some(n) = near {
+                                let rows = t.rows();
+                                if view.1 .1 >= rows - 1 - n
+                                    && asked_rows.replace(rows) != Some(rows)
+                                {
+                                    let mut this = this.clone();
+                                    app::add_timeout3(0.0, move |_| {
+                                        let last = this.view_to_data_row(rows - 1);
+                                        let cb = this.on_need_more_rows.clone();
+                                        if let Some((_, cb)) = &mut *cb.try_lock().unwrap() {
+                                            cb(&mut this, last);
+                                        };
+                                    });
+                                }
+                            }
+                            // The page or the number of pages changed, e.g. rows were appended
+                            let paging = filter
+                                .try_lock()
+                                .ok()
+                                .and_then(|f| f.as_ref().map(|f| (f.page, f.passing)));
+                            if last_paging.replace(paging).is_some_and(|p| p != paging) {
+                                this.update_pager_later();
+                            }
+                            #[cfg(feature = "editing")]
+                            {
+                                // The edited cell moved, the editor follows it once drawing ends
+                                let geometry = (
+                                    (t.x(), t.y(), t.w(), t.h()),
+                                    (t.scrollbar().value(), t.hscrollbar().value()),
+                                );
+                                if last_geometry
+                                    .replace(geometry)
+                                    .is_some_and(|g| g != geometry)
+                                    && this.is_editing()
+                                {
+                                    let mut this = this.clone();
+                                    app::add_timeout3(0.0, move |_| this.follow_editor());
+                                }
+                            }
+                            // Picks up the style setters' changes
+                            if let Ok(o) = this.opts.try_lock() {
+                                base_opts = *o;
                             }
-                            Self::draw_data(
-                                &data[row as usize][col as usize],
+                            opts = base_opts;
+                            if base_opts.dpi_aware {
+                                let factor = Self::dpi_factor(t);
+                                *scale.borrow_mut() = factor;
+                                opts = base_opts.scaled(factor);
+                            }
+                            if base_opts.theme_colors {
+                                opts = opts.themed();
+                            }
+                            if opts.follow_tail {
+                                let rows = t.rows();
+                                let sb = t.scrollbar();
+                                let mut at_bottom =
+                                    !sb.visible() || sb.value() >= sb.maximum() - 1.0;
+                                if let Some((last_rows, true)) = tail {
+                                    if rows > last_rows && !at_bottom {
+                                        // Scrolled after drawing, then drawn again
+                                        let mut this = this.clone();
+                                        app::add_timeout3(0.0, move |_| {
+                                            let col = this.visible_range().1;
+                                            this.ensure_visible(rows - 1, col);
+                                        });
+                                        at_bottom = true;
+                                    }
+                                }
+                                tail = Some((rows, at_bottom));
+                            }
+                            bands = match &*row_band.try_lock().unwrap() {
+                                Some(RowBand::Col(col, colors)) => {
+                                    Self::col_bands(&data, *col, colors)
+                                }
+                                _ => vec![],
+                            };
+                            Self::fit_wrapped_rows(
+                                t,
+                                &data,
+                                model,
+                                &col_props,
+                                &filter.try_lock().unwrap(),
+                                &opts,
+                                &mut grown,
+                            );
+                            tree_shown = this
+                                .tree
+                                .try_lock()
+                                .is_ok_and(|nodes| nodes.iter().any(Option::is_some));
+                            header_band = 0;
+                            if let Ok(mut filter_row) = this.filter_row.try_lock() {
+                                if let Some(filter_row) = filter_row.as_mut() {
+                                    header_band = filter_row.band;
+                                    this.place_filter_inputs(t, filter_row, &col_props, opts.rtl);
+                                }
+                            }
+                            let fh = Self::footer_height(t, &col_props);
+                            Self::fit_footer_room(t, fh, &mut footer_room);
+                            draw::set_font(Font::Helvetica, 14)
+                        }
+                        table::TableContext::ColHeader | table::TableContext::Cell if hidden => {}
+                        table::TableContext::ColHeader => {
+                            let h = h - header_band;
+                            Self::draw_header(
+                                &data_col.map_or(String::new(), |c| match model {
+                                    Some(m) => m.col_header(c),
+                                    None => col_headers[c as usize].clone(),
+                                }),
                                 x,
                                 y,
                                 w,
                                 h,
-                                t.is_selected(row, col),
                                 &opts,
                             );
+                            // The sort arrow moves left of the dropdown arrow
+                            let sort_w = match data_col {
+                                Some(col) if opts.filter_dropdowns => {
+                                    let filtered = filter.try_lock().is_ok_and(|f| {
+                                        f.as_ref()
+                                            .is_some_and(|f| f.col_excluded.contains_key(&col))
+                                    });
+                                    Self::draw_dropdown_arrow(filtered, x, y, w, h, &opts);
+                                    w - Self::dropdown_width(&opts)
+                                }
+                                _ => w,
+                            };
+                            let sorted = this.sort.try_lock().ok().and_then(|s| s.current);
+                            if let (Some((c, order)), Some(col)) = (sorted, data_col) {
+                                if c == col {
+                                    Self::draw_sort_arrow(order, x, y, sort_w, h, &opts);
+                                }
+                            }
+                        } // Column titles
+                        table::TableContext::RowHeader => {
+                            Self::draw_header(&row_header(row), x, y, w, h, &opts.row_headers())
+                        } // Row titles
+                        table::TableContext::Cell => {
+                            let Some(data_col) = data_col else {
+                                // Row titles of the right-to-left layout
+                                Self::draw_header(
+                                    &row_header(row),
+                                    x,
+                                    y,
+                                    w,
+                                    h,
+                                    &opts.row_headers(),
+                                );
+                                return;
+                            };
+                            let cell_selected = t.is_selected(view_row, col);
+                            if cell_selected {
+                                cell.borrow_mut().select(row, data_col, x, y, w, h);
+                                // Captures the cell information
+                            }
+                            let selected = match opts.selection_mode {
+                                SelectionMode::Cell => cell_selected,
+                                SelectionMode::Row | SelectionMode::MultiRow => {
+                                    t.row_selected(view_row)
+                                }
+                            };
+                            let focus = Self::focus_of(t);
+                            let tinted = !selected && focus.is_some_and(|s| s.0 == view_row);
+                            let model_cell;
+                            let target = match model {
+                                Some(m) => {
+                                    model_cell = Cell::from_string(m.cell(row, data_col));
+                                    &model_cell
+                                }
+                                None => &data[row as usize][data_col as usize],
+                            };
+                            let highlighted;
+                            let target = if found
+                                .try_lock()
+                                .is_ok_and(|f| f.matches.contains(&(row, data_col)))
+                            {
+                                highlighted = Cell {
+                                    color: Some(opts.find_color),
+                                    ..target.clone()
+                                };
+                                &highlighted
+                            } else {
+                                target
+                            };
+                            let band = match &*row_band.try_lock().unwrap() {
+                                Some(RowBand::Fn(f)) => {
+                                    data.get(row as usize).and_then(|r| f(row, r))
+                                }
+                                Some(RowBand::Col(..)) => bands.get(row as usize).copied(),
+                                None => None,
+                            }
+                            .or(opts.alt_row_color.filter(|_| view_row % 2 == 1));
+                            let props = &col_props[data_col as usize];
+                            // The first column of a tree is indented by the row's level, the
+                            // background runs under the indentation
+                            let indent = match this.tree.try_lock() {
+                                Ok(nodes) if tree_shown && data_col == 0 => {
+                                    let blank = Cell {
+                                        label: String::new(),
+                                        value: None,
+                                        link: false,
+                                        ..target.clone()
+                                    };
+                                    Self::draw_data(
+                                        &blank, props, x, y, w, h, selected, tinted, band, &opts,
+                                    );
+                                    Self::draw_branch(&nodes, row, x, y, h, &opts)
+                                }
+                                _ => 0,
+                            };
+                            let (cx, cw) = (x + indent, w - indent);
+                            if let Some(f) = &*cell_draw.try_lock().unwrap() {
+                                draw::push_clip(cx, y, cw, h);
+                                f(&mut DrawCtx {
+                                    row,
+                                    col: data_col,
+                                    x: cx,
+                                    y,
+                                    w: cw,
+                                    h,
+                                    value: &target.label,
+                                    selected,
+                                    cell: target,
+                                    props,
+                                    tinted,
+                                    band,
+                                    opts: &opts,
+                                });
+                                draw::pop_clip();
+                            } else {
+                                Self::draw_data(
+                                    target, props, cx, y, cw, h, selected, tinted, band, &opts,
+                                );
+                            }
+                            if cell_selected {
+                                Self::draw_selection_decor(
+                                    t,
+                                    view_row,
+                                    col,
+                                    x,
+                                    y,
+                                    w,
+                                    h,
+                                    focus == Some((view_row, col)),
+                                    &opts,
+                                );
+                            }
                         }
                         _ => (),
                     }
                 }
             }
+        };
+        self.table.draw_cell({
+            let frozen_rows = self.frozen_rows.clone();
+            move |t, ctx, row, col, x, y, w, h| {
+                if let table::TableContext::EndPage = ctx {
+                    let n = *frozen_rows.try_lock().unwrap();
+                    Self::draw_frozen_rows(t, &mut draw_cell, n, (x, y, w, h));
+                }
+                draw_cell(t, ctx, row, col, x, y, w, h)
+            }
         });
 
-        if opts.editable {
-            self.inp = Some(input::Input::default());
-            let mut inp = self.inp.as_ref().unwrap().clone();
-            inp.set_trigger(CallbackTrigger::EnterKey);
-            let win = window::Window::from_dyn_widget_ptr(
-                self.table.top_window().unwrap().as_widget_ptr(),
-            );
-            win.unwrap().add(&inp);
-            inp.hide();
-
-            inp.set_callback({
-                let cell = cell.clone();
-                let data = self.data.clone();
-                let mut table = self.table.clone();
-                let on_update_callback = self.on_update_callback.clone();
-                move |i| {
-                    let cell = cell.borrow();
-                    on_update_callback.try_lock().unwrap()(cell.row, cell.col, i.value());
-                    data.try_lock().unwrap()[cell.row as usize][cell.col as usize].label =
-                        i.value();
-                    i.set_value("");
-                    i.hide();
-                    table.redraw();
-                }
-            });
-
-            inp.handle(|i, ev| match ev {
-                Event::KeyUp => {
-                    if app::event_key() == Key::Escape {
-                        i.hide();
-                        true
-                    } else {
-                        false
+        self.table.resize_callback({
+            let col_props = self.col_props.clone();
+            let opts = self.opts.clone();
+            move |t, _, _, _, _| {
+                let opts = *opts.try_lock().unwrap();
+                Self::apply_width_modes(t, &col_props.try_lock().unwrap(), &opts);
+            }
+        });
+
+        self.table.handle({
+            let data = self.data.clone();
+            let row_headers = self.row_headers.clone();
+            let col_headers = self.col_headers.clone();
+            let announce_callback = self.announce_callback.clone();
+            let col_props = self.col_props.clone();
+            let model = self.model.clone();
+            let filter = self.filter.clone();
+            let mut this = self.clone();
+            let mut last_focus = None;
+            let mut last_selection = (vec![], None);
+            let mut hovering_link = false;
+            let mut resizing = None;
+            let drag = Rc::from(RefCell::from(DragState::default()));
+            let touch = Rc::from(RefCell::from(TouchState::default()));
+            move |t, ev| {
+                if opts.touch_scroll && Self::handle_touch_scroll(t, ev, &touch) {
+                    return true;
+                }
+                if Self::handle_frozen_click(t, ev, this.frozen_rows()) {
+                    return true;
+                }
+                if this.handle_link(t, ev, opts.rtl, &mut hovering_link) {
+                    return true;
+                }
+                if this.handle_tree_click(t, ev, opts.rtl) {
+                    return true;
+                }
+                if opts.filter_dropdowns && this.handle_filter_dropdown(t, ev, opts.rtl) {
+                    return true;
+                }
+                if Self::handle_drag_scroll(t, ev, &drag) {
+                    return true;
+                }
+                if this.handle_autosize_click(t, ev, opts.rtl) {
+                    return true;
+                }
+                if opts.dpi_aware
+                    && Self::handle_scaled_resize(t, ev, *scale.borrow(), &mut resizing)
+                {
+                    return true;
+                }
+                if matches!(ev, Event::KeyUp | Event::Released) {
+                    let selection = (
+                        this.selected_rows(),
+                        t.try_get_selection().filter(|s| s.0 >= 0 && s.1 >= 0),
+                    );
+                    if selection != last_selection {
+                        last_selection = selection;
+                        let slot = this.on_selection_changed.clone();
+                        let mut slot = slot.try_lock().unwrap();
+                        if let Some(cb) = &mut *slot {
+                            cb(&mut this);
+                        }
                     }
                 }
-                _ => false,
-            });
-
-            self.table.handle({
-                let data = self.data.clone();
-                move |_, ev| match ev {
+                if matches!(ev, Event::Focus | Event::KeyUp | Event::Released) {
+                    if ev == Event::Focus {
+                        last_focus = None;
+                    }
+                    let focus = Self::focus_of(t);
+                    if focus.is_some() && focus != last_focus {
+                        last_focus = focus;
+                        // Described with the data locked, announced once it's released
+                        let desc = focus.zip(data.try_lock().ok()).map(|((row, col), data)| {
+                            let row = Self::data_row(&filter.try_lock().unwrap(), row);
+                            let col = Self::data_col(opts.rtl, t.cols() - opts.rtl as i32, col);
+                            match (model.try_lock().unwrap().as_deref(), col) {
+                                (Some(m), Some(col)) => format!(
+                                    "Row {}, column {}: {}",
+                                    m.row_header(row),
+                                    m.col_header(col),
+                                    m.cell(row, col)
+                                ),
+                                _ => Self::describe(
+                                    &data,
+                                    &row_headers.try_lock().unwrap(),
+                                    &col_headers.try_lock().unwrap(),
+                                    row,
+                                    col.unwrap_or(-1),
+                                ),
+                            }
+                        });
+                        if let Some(desc) = desc {
+                            announce_callback.try_lock().unwrap()(&desc);
+                        }
+                    }
+                }
+                match ev {
+                    Event::KeyDown if app::is_event_ctrl() => match app::event_key() {
+                        k if k == Key::from_char('c') => this.copy_selection(),
+                        k if k == Key::from_char('z') => this.undo(),
+                        k if k == Key::from_char('y') => this.redo(),
+                        _ if opts.resize_step > 0 => {
+                            let step = (opts.resize_step as f32 * *scale.borrow()).round() as i32;
+                            Self::resize_with_keys(t, step)
+                        }
+                        _ => false,
+                    },
+                    #[cfg(feature = "editing")]
+                    Event::KeyDown
+                        if opts.editable
+                            && matches!(app::event_key(), Key::Enter | Key::KPEnter | Key::F2) =>
+                    {
+                        this.edit_focused(&cell)
+                    }
+                    Event::KeyDown
+                        if opts.editable
+                            && opts.delete_clears
+                            && matches!(app::event_key(), Key::Delete | Key::BackSpace) =>
+                    {
+                        this.clear_selection_contents()
+                    }
+                    #[cfg(feature = "editing")]
+                    Event::KeyDown if opts.editable && app::event_key() == Key::Tab => {
+                        this.edit_next(&cell, !app::is_event_shift())
+                    }
+                    #[cfg(feature = "editing")]
+                    Event::KeyDown
+                        if opts.editable
+                            && opts.type_to_edit
+                            && !app::is_event_alt()
+                            && !app::is_event_command() =>
+                    {
+                        let text = app::event_text();
+                        !text.is_empty()
+                            && !text.chars().any(char::is_control)
+                            && this.type_to_edit(&cell, &text)
+                    }
+                    Event::MouseWheel => Self::handle_wheel(t, opts.smooth_scroll, *scale.borrow()),
+                    Event::Move | Event::Enter => {
+                        this.header_tooltip(t, &col_props.try_lock().unwrap(), opts.rtl);
+                        this.cell_tooltip(t, opts.rtl);
+                        false
+                    }
+                    Event::Push if opts.col_chooser => this.handle_col_chooser(t),
                     Event::Released => {
-                        if let Ok(data) = data.try_lock() {
+                        #[cfg(feature = "editing")]
+                        if opts.rename_headers && this.handle_header_rename(t, ev, opts.rtl) {
+                            return true;
+                        }
+                        if this.handle_sort_click(t, ev, opts.rtl) {
+                            return true;
+                        }
+                        let Ok(data) = data.try_lock() else {
+                            return false;
+                        };
+                        Self::copy_to_primary(
+                            t,
+                            &data,
+                            model.try_lock().unwrap().as_deref(),
+                            &filter.try_lock().unwrap(),
+                            opts.rtl,
+                        );
+                        drop(data);
+                        #[cfg(feature = "editing")]
+                        if opts.editable
+                            && match opts.edit_trigger {
+                                EditTrigger::SingleClick => true,
+                                EditTrigger::DoubleClick => app::event_clicks_num() > 0,
+                                EditTrigger::EnterKey => false,
+                            }
+                        {
+                            let min_h = (opts.cell_font_size as f32 * 1.5 * *scale.borrow()) as i32;
                             let cell = cell.borrow();
-                            inp.resize(cell.x, cell.y, cell.w, cell.h);
-                            inp.set_value(&data[cell.row as usize][cell.col as usize].label);
-                            inp.show();
-                            inp.take_focus().ok();
-                            inp.redraw();
-                            true
-                        } else {
-                            false
+                            let text = this.edit_text(cell.row, cell.col);
+                            return this.open_editor(text, &cell, min_h);
                         }
+                        false
                     }
                     _ => false,
                 }
-            });
+            }
+        });
+    }
+
+    // Spreadsheet-like column names: A, B, ... Z, AA, AB, ... ZZ, AAA, ...
+    fn default_col_header(i: i32) -> String {
+        let mut name = vec![];
+        let mut n = i + 1;
+        while n > 0 {
+            n -= 1;
+            name.push(b'A' + (n % 26) as u8);
+            n /= 26;
+        }
+        name.reverse();
+        String::from_utf8(name).unwrap()
+    }
+
+    // The default header of the row, see `set_row_header_label()`
+    fn row_label(&self, i: i32) -> String {
+        match &*self.row_header_label.try_lock().unwrap() {
+            Some(f) => f(i),
+            None => (i + 1).to_string(),
+        }
+    }
+
+    // The default header of the column, see `set_col_header_label()`
+    fn col_label(&self, i: i32) -> String {
+        match &*self.col_header_label.try_lock().unwrap() {
+            Some(f) => f(i),
+            None => Self::default_col_header(i),
         }
     }
 
@@ -297,6 +1410,7 @@ impl SmartTable {
     }
 
     /// Get the input widget
+    #[cfg(feature = "editing")]
     pub fn input(&mut self) -> &mut Option<input::Input> {
         &mut self.inp
     }
@@ -311,284 +1425,2627 @@ impl SmartTable {
         self.data.clone()
     }
 
-    fn draw_header(txt: &str, x: i32, y: i32, w: i32, h: i32, opts: &TableOpts) {
-        draw::push_clip(x, y, w, h);
-        draw::draw_box(opts.header_frame, x, y, w, h, opts.header_color);
-        draw::set_draw_color(opts.header_font_color);
-        draw::set_font(opts.header_font, opts.header_font_size);
-        draw::draw_text2(txt, x, y, w, h, opts.header_align);
-        draw::pop_clip();
+    // The scale factor to apply on top of FLTK's own screen scaling, derived from the DPI
+    // of the screen the table is on
+    fn dpi_factor(t: &table::TableRow) -> f32 {
+        let (wx, wy) = t.window().map_or((0, 0), |w| (w.x_root(), w.y_root()));
+        let n = app::screen_num(wx + t.x() + t.w() / 2, wy + t.y() + t.h() / 2);
+        let (dpi, _) = app::screen_dpi(n);
+        (dpi / 96.0 / app::screen_scale(n)).max(1.0)
     }
 
-    // The selected flag sets the color of the cell to a grayish color, otherwise white
-    fn draw_data(cell: &Cell, x: i32, y: i32, w: i32, h: i32, selected: bool, opts: &TableOpts) {
-        draw::push_clip(x, y, w, h);
-        let sel_col = if let Some(sel_col) = cell.selection_color {
-            sel_col
-        } else {
-            opts.cell_selection_color
-        };
-        let bg = if let Some(col) = cell.color {
-            col
-        } else {
-            opts.cell_color
-        };
-        if selected {
-            draw::set_draw_color(sel_col);
-        } else {
-            draw::set_draw_color(bg);
-        }
-        draw::draw_rectf(x, y, w, h);
-        draw::set_draw_color(if let Some(col) = cell.font_color {
-            col
-        } else {
-            opts.cell_font_color
-        });
-        draw::set_font(
-            if let Some(font) = cell.font {
-                font
-            } else {
-                opts.cell_font
-            },
-            if let Some(font) = cell.font_size {
-                font
-            } else {
-                opts.cell_font_size
-            },
-        );
-        draw::draw_text2(
-            &cell.label,
-            x + opts.cell_padding,
-            y,
-            w - opts.cell_padding * 2,
-            h,
-            if let Some(a) = cell.align {
-                a
-            } else {
-                opts.cell_align
+    // FLTK only grabs header dividers within 3 pixels, widen that zone by the scale factor
+    fn handle_scaled_resize(
+        t: &mut table::TableRow,
+        ev: Event,
+        factor: f32,
+        resizing: &mut Option<(table::TableContext, i32, i32)>,
+    ) -> bool {
+        let zone = (3.0 * factor).round() as i32;
+        match ev {
+            Event::Move | Event::Push => {
+                let (ex, ey) = app::event_coords();
+                let hit = match t.cursor2rowcol() {
+                    Some((
+                        ctx @ table::TableContext::ColHeader,
+                        _,
+                        col,
+                        table::TableResizeFlag::None,
+                    )) => t.find_cell(ctx, 0, col).and_then(|(x, _, w, _)| {
+                        if x + w - ex <= zone {
+                            Some((ctx, col, ex - w))
+                        } else if ex - x <= zone && col > 0 {
+                            Some((ctx, col - 1, ex - t.col_width(col - 1)))
+                        } else {
+                            None
+                        }
+                    }),
+                    Some((
+                        ctx @ table::TableContext::RowHeader,
+                        row,
+                        _,
+                        table::TableResizeFlag::None,
+                    )) => t.find_cell(ctx, row, 0).and_then(|(_, y, _, h)| {
+                        if y + h - ey <= zone {
+                            Some((ctx, row, ey - h))
+                        } else if ey - y <= zone && row > 0 {
+                            Some((ctx, row - 1, ey - t.row_height(row - 1)))
+                        } else {
+                            None
+                        }
+                    }),
+                    _ => None,
+                };
+                let cursor = match hit {
+                    Some((table::TableContext::ColHeader, ..)) => Cursor::WE,
+                    Some(_) => Cursor::NS,
+                    None => return false,
+                };
+                if let Some(mut win) = t.window() {
+                    win.set_cursor(cursor);
+                }
+                if ev == Event::Push {
+                    *resizing = hit;
+                }
+                true
+            }
+            Event::Drag => match *resizing {
+                Some((table::TableContext::ColHeader, col, origin)) => {
+                    let w = (app::event_x() - origin).max(t.col_resize_min());
+                    t.set_col_width(col, w);
+                    true
+                }
+                Some((_, row, origin)) => {
+                    let h = (app::event_y() - origin).max(t.row_resize_min());
+                    t.set_row_height(row, h);
+                    true
+                }
+                None => false,
             },
-        );
-        draw::set_draw_color(if let Some(col) = cell.border_color {
-            col
-        } else {
-            opts.cell_border_color
-        });
-        draw::draw_rect(x, y, w, h);
-        draw::pop_clip();
+            Event::Released => resizing.take().is_some(),
+            _ => false,
+        }
     }
 
-    /// Set the cell value, using the row and column to index the data
-    pub fn set_cell_value(&mut self, row: i32, col: i32, val: &str) {
-        self.data.try_lock().unwrap()[row as usize][col as usize].label = val.to_string();
+    // The area of the table where cells are drawn, excluding headers and scrollbars
+    fn cells_area(t: &table::TableRow) -> (i32, i32, i32, i32) {
+        let frame = t.frame();
+        let mut x0 = t.x() + frame.dx();
+        let mut y0 = t.y() + frame.dy();
+        let mut x1 = t.x() + t.w() - (frame.dw() - frame.dx());
+        let mut y1 = t.y() + t.h() - (frame.dh() - frame.dy());
+        if t.row_header() {
+            x0 += t.row_header_width();
+        }
+        if t.col_header() {
+            y0 += t.col_header_height();
+        }
+        if t.scrollbar().visible() {
+            x1 -= t.scrollbar_size().max(app::scrollbar_size());
+        }
+        if t.hscrollbar().visible() {
+            y1 -= t.scrollbar_size().max(app::scrollbar_size());
+        }
+        (x0, y0, x1, y1)
     }
 
-    /// Get the cell value, using the row and column to index the data
-    pub fn cell_value(&self, row: i32, col: i32) -> String {
-        self.data.try_lock().unwrap()[row as usize][col as usize]
+    // The visible cell under the point, clamped into the cells area
+    fn cell_at(t: &table::TableRow, x: i32, y: i32) -> Option<(i32, i32)> {
+        let (x0, y0, x1, y1) = Self::cells_area(t);
+        let (x, y) = (x.clamp(x0, x1 - 1), y.clamp(y0, y1 - 1));
+        let (r1, r2, c1, c2) = t.try_visible_cells()?;
+        let row = (r1..=r2).find(|r| {
+            t.find_cell(table::TableContext::Cell, *r, c1)
+                .is_some_and(|(_, cy, _, ch)| y >= cy && y < cy + ch)
+        })?;
+        let col = (c1..=c2).find(|c| {
+            t.find_cell(table::TableContext::Cell, r1, *c)
+                .is_some_and(|(cx, _, cw, _)| x >= cx && x < cx + cw)
+        })?;
+        Some((row, col))
+    }
+
+    // Scrolls towards the pointer when it's past the cells area, and extends the selection
+    // from the drag anchor. Returns whether the pointer is still outside the cells
+    fn auto_scroll_step(t: &mut table::TableRow, anchor: (i32, i32)) -> bool {
+        let (x0, y0, x1, y1) = Self::cells_area(t);
+        let (ex, ey) = app::event_coords();
+        let overshoot = |v: i32, lo: i32, hi: i32| {
+            if v < lo {
+                (v - lo).clamp(-60, -4)
+            } else if v >= hi {
+                (v - hi).clamp(4, 60)
+            } else {
+                0
+            }
+        };
+        let (dx, dy) = (overshoot(ex, x0, x1), overshoot(ey, y0, y1));
+        if dx == 0 && dy == 0 {
+            return false;
+        }
+        for (mut sb, delta) in [(t.hscrollbar(), dx), (t.scrollbar(), dy)] {
+            if delta != 0 && sb.visible() {
+                let (lo, hi) = (
+                    sb.minimum().min(sb.maximum()),
+                    sb.minimum().max(sb.maximum()),
+                );
+                sb.set_value((sb.value() + delta as f64).clamp(lo, hi));
+                sb.do_callback();
+            }
+        }
+        if let Some((row, col)) = Self::cell_at(t, ex, ey) {
+            t.set_selection(anchor.0, anchor.1, row, col);
+        }
+        t.redraw();
+        true
+    }
+
+    // Keeps drag-selections going past the edges of the viewport
+    fn handle_drag_scroll(
+        t: &mut table::TableRow,
+        ev: Event,
+        drag: &Rc<RefCell<DragState>>,
+    ) -> bool {
+        match ev {
+            Event::Push => {
+                drag.borrow_mut().anchor = match t.cursor2rowcol() {
+                    Some((table::TableContext::Cell, row, col, _)) => Some((row, col)),
+                    _ => None,
+                };
+                false
+            }
+            Event::Drag => {
+                let Some(anchor) = drag.borrow().anchor else {
+                    return false;
+                };
+                let (x0, y0, x1, y1) = Self::cells_area(t);
+                let (ex, ey) = app::event_coords();
+                if ex >= x0 && ex < x1 && ey >= y0 && ey < y1 {
+                    return false;
+                }
+                if drag.borrow().timer.is_none() {
+                    let mut t = t.clone();
+                    let state = drag.clone();
+                    let handle = app::add_timeout3(0.05, move |handle| {
+                        let held = app::event_state().contains(EventState::Button1);
+                        if held && Self::auto_scroll_step(&mut t, anchor) {
+                            app::repeat_timeout3(0.05, handle);
+                        } else {
+                            state.borrow_mut().timer = None;
+                        }
+                    });
+                    drag.borrow_mut().timer = Some(handle);
+                }
+                true
+            }
+            Event::Released => {
+                let mut drag = drag.borrow_mut();
+                drag.anchor = None;
+                if let Some(handle) = drag.timer.take() {
+                    app::remove_timeout3(handle);
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    // Moves both scrollbars by the given number of pixels
+    fn scroll_by(t: &table::TableRow, dx: f64, dy: f64) {
+        for (mut sb, delta) in [(t.hscrollbar(), dx), (t.scrollbar(), dy)] {
+            if delta != 0.0 && sb.visible() {
+                let (lo, hi) = (
+                    sb.minimum().min(sb.maximum()),
+                    sb.minimum().max(sb.maximum()),
+                );
+                sb.set_value((sb.value() + delta).clamp(lo, hi));
+                sb.do_callback();
+            }
+        }
+    }
+
+    // Pans the view when dragging on the cells, then lets it glide to a stop
+    fn handle_touch_scroll(
+        t: &mut table::TableRow,
+        ev: Event,
+        touch: &Rc<RefCell<TouchState>>,
+    ) -> bool {
+        const TAP_TRAVEL: i32 = 8;
+        const FRICTION: f64 = 0.92;
+        const TICK: f64 = 0.016;
+        match ev {
+            Event::Push => {
+                let Some((table::TableContext::Cell, row, col, _)) = t.cursor2rowcol() else {
+                    return false;
+                };
+                let mut state = touch.borrow_mut();
+                if let Some(handle) = state.timer.take() {
+                    app::remove_timeout3(handle);
+                }
+                let (x, y) = app::event_coords();
+                state.last = Some((x, y, std::time::Instant::now()));
+                state.travel = 0;
+                state.velocity = (0.0, 0.0);
+                t.set_selection(row, col, row, col);
+                t.redraw();
+                true
+            }
+            Event::Drag => {
+                let mut state = touch.borrow_mut();
+                let Some((lx, ly, lt)) = state.last else {
+                    return false;
+                };
+                let (x, y) = app::event_coords();
+                let (dx, dy) = ((lx - x) as f64, (ly - y) as f64);
+                Self::scroll_by(t, dx, dy);
+                let dt = lt.elapsed().as_secs_f64().max(0.001);
+                // Velocity in pixels per tick, smoothed over the last few moves
+                let v = (dx / dt * TICK, dy / dt * TICK);
+                state.velocity = (
+                    0.5 * state.velocity.0 + 0.5 * v.0,
+                    0.5 * state.velocity.1 + 0.5 * v.1,
+                );
+                state.travel += (lx - x).abs() + (ly - y).abs();
+                state.last = Some((x, y, std::time::Instant::now()));
+                true
+            }
+            Event::Released => {
+                let mut state = touch.borrow_mut();
+                let Some((_, _, lt)) = state.last.take() else {
+                    return false;
+                };
+                if state.travel < TAP_TRAVEL {
+                    // A tap, let the release through so it behaves like a click
+                    return false;
+                }
+                if lt.elapsed().as_secs_f64() < 0.1 {
+                    let t = t.clone();
+                    let touch = touch.clone();
+                    let handle = app::add_timeout3(TICK, move |handle| {
+                        let mut state = touch.borrow_mut();
+                        let (vx, vy) = state.velocity;
+                        if vx.abs() < 0.5 && vy.abs() < 0.5 {
+                            state.timer = None;
+                            return;
+                        }
+                        Self::scroll_by(&t, vx, vy);
+                        state.velocity = (vx * FRICTION, vy * FRICTION);
+                        app::repeat_timeout3(TICK, handle);
+                    });
+                    state.timer = Some(handle);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Scrolls horizontally on Shift+wheel and horizontal trackpad swipes,
+    // and by pixels rather than rows in smooth mode
+    fn handle_wheel(t: &mut table::TableRow, smooth: bool, factor: f32) -> bool {
+        const SMOOTH_STEP: f32 = 8.0;
+        let (mut dx, mut dy) = (app::event_dx_value(), app::event_dy_value());
+        if app::is_event_shift() && dx == 0 {
+            dx = dy;
+            dy = 0;
+        }
+        let step = (SMOOTH_STEP * factor).round() as f64;
+        let scroll = |mut sb: fltk::valuator::Scrollbar, delta: i32| {
+            if delta != 0 && sb.visible() {
+                let (lo, hi) = (
+                    sb.minimum().min(sb.maximum()),
+                    sb.minimum().max(sb.maximum()),
+                );
+                sb.set_value((sb.value() + delta as f64 * step).clamp(lo, hi));
+                sb.do_callback();
+            }
+        };
+        if smooth {
+            scroll(t.hscrollbar(), dx);
+            scroll(t.scrollbar(), dy);
+            true
+        } else if dx != 0 {
+            let col = (t.col_position() + dx).clamp(0, (t.cols() - 1).max(0));
+            t.set_col_position(col);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Ctrl+arrows resize the focused column or row
+    fn resize_with_keys(t: &mut table::TableRow, step: i32) -> bool {
+        let Some((row, col)) = Self::focus_of(t) else {
+            return false;
+        };
+        match app::event_key() {
+            Key::Left => t.set_col_width(col, (t.col_width(col) - step).max(t.col_resize_min())),
+            Key::Right => t.set_col_width(col, t.col_width(col) + step),
+            Key::Up => t.set_row_height(row, (t.row_height(row) - step).max(t.row_resize_min())),
+            Key::Down => t.set_row_height(row, t.row_height(row) + step),
+            _ => return false,
+        }
+        t.redraw();
+        true
+    }
+
+    // The focused cell is the anchor (top-left) of the current selection
+    fn focus_of(t: &table::TableRow) -> Option<(i32, i32)> {
+        t.try_get_selection()
+            .filter(|s| s.0 >= 0 && s.1 >= 0)
+            .map(|s| (s.0, s.1))
+    }
+
+    fn describe(
+        data: &CellMatrix,
+        row_headers: &[String],
+        col_headers: &[String],
+        row: i32,
+        col: i32,
+    ) -> String {
+        format!(
+            "Row {}, column {}: {}",
+            row_headers.get(row as usize).map_or("", |h| h.as_str()),
+            col_headers.get(col as usize).map_or("", |h| h.as_str()),
+            data.get(row as usize)
+                .and_then(|r| r.get(col as usize))
+                .map_or("", |c| c.label.as_str())
+        )
+    }
+
+    // The cell of the model, made from its text, or of the data. None outside them
+    fn source_cell<'a>(
+        data: &'a CellMatrix,
+        model: Option<&dyn TableModel>,
+        row: i32,
+        col: i32,
+    ) -> Option<Cow<'a, Cell>> {
+        match model {
+            Some(m) => ((0..m.row_count()).contains(&row) && (0..m.col_count()).contains(&col))
+                .then(|| Cow::Owned(Cell::from_string(m.cell(row, col)))),
+            None => data.get(row as usize)?.get(col as usize).map(Cow::Borrowed),
+        }
+    }
+
+    // Rows and columns of the model or of the data
+    fn source_size(data: &CellMatrix, model: Option<&dyn TableModel>) -> (i32, i32) {
+        match model {
+            Some(m) => (m.row_count(), m.col_count()),
+            None => (
+                data.len() as i32,
+                data.first().map_or(0, |r| r.len()) as i32,
+            ),
+        }
+    }
+
+    // Maps a column of the inner table to its data column. In right-to-left mode the
+    // columns are mirrored, and the trailing column holding the row headers maps to None
+    fn data_col(rtl: bool, cols: i32, col: i32) -> Option<i32> {
+        if !rtl {
+            Some(col)
+        } else if col < cols {
+            Some(cols - 1 - col)
+        } else {
+            None
+        }
+    }
+
+    // Inverse of data_col
+    fn display_col(rtl: bool, cols: i32, col: i32) -> i32 {
+        if rtl {
+            cols - 1 - col
+        } else {
+            col
+        }
+    }
+
+    // Tab-separated text of the cells within the given selection bounds, in data column order
+    fn range_text(
+        data: &CellMatrix,
+        model: Option<&dyn TableModel>,
+        filter: &Option<filter::RowFilter>,
+        (r1, c1, r2, c2): (i32, i32, i32, i32),
+        rtl: bool,
+    ) -> String {
+        let (nrows, ncols) = Self::source_size(data, model);
+        let mut cols: Vec<i32> = (c1..=c2)
+            .filter_map(|c| Self::data_col(rtl, ncols, c))
+            .collect();
+        cols.sort_unstable();
+        let mut txt = String::new();
+        for row in (r1..=r2)
+            .map(|r| Self::data_row(filter, r))
+            .filter(|r| *r < nrows)
+        {
+            let cells: Vec<String> = cols
+                .iter()
+                .filter_map(|c| Self::source_cell(data, model, row, *c))
+                .map(|c| c.label.clone())
+                .collect();
+            txt.push_str(&cells.join("\t"));
+            txt.push('\n');
+        }
+        txt
+    }
+
+    // Populates the X11 primary selection with the selected cells, so they can be middle-click pasted
+    fn copy_to_primary(
+        t: &table::TableRow,
+        data: &CellMatrix,
+        model: Option<&dyn TableModel>,
+        filter: &Option<filter::RowFilter>,
+        rtl: bool,
+    ) {
+        if cfg!(all(unix, not(target_os = "macos"))) {
+            if let Some(sel) = t.try_get_selection().filter(|s| s.0 >= 0 && s.1 >= 0) {
+                app::copy2(&Self::range_text(data, model, filter, sel, rtl));
+            }
+        }
+    }
+
+    fn draw_header(txt: &str, x: i32, y: i32, w: i32, h: i32, opts: &TableOpts) {
+        draw::push_clip(x, y, w, h);
+        draw::draw_box(opts.header_frame, x, y, w, h, opts.header_color);
+        draw::set_draw_color(opts.header_font_color);
+        draw::set_font(opts.header_font, opts.header_font_size);
+        draw::draw_text2(txt, x, y, w, h, opts.header_align);
+        draw::pop_clip();
+    }
+
+    // Draws a boolean cell as a checkbox centered in the cell
+    fn draw_checkbox(checked: bool, x: i32, y: i32, w: i32, h: i32, color: Color) {
+        let s = (h - 6).clamp(0, 14).min(w - 4);
+        let (bx, by) = (x + (w - s) / 2, y + (h - s) / 2);
+        draw::draw_box(FrameType::DownBox, bx, by, s, s, Color::BackGround2);
+        if checked {
+            draw::draw_check(bx + 2, by + 2, s - 4, s - 4, color);
+        }
+    }
+
+    // The selected flag sets the color of the cell to a grayish color, otherwise white
+    #[allow(clippy::too_many_arguments)]
+    fn draw_data(
+        cell: &Cell,
+        props: &ColProps,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        selected: bool,
+        tinted: bool,
+        band: Option<Color>,
+        opts: &TableOpts,
+    ) {
+        draw::push_clip(x, y, w, h);
+        let sel_col = if let Some(sel_col) = cell.selection_color {
+            sel_col
+        } else {
+            opts.cell_selection_color
+        };
+        let bg = cell.color.or(band).unwrap_or(opts.cell_color);
+        if selected {
+            draw::set_draw_color(sel_col);
+        } else if let (true, Some(tint)) = (tinted, opts.row_tint) {
+            draw::set_draw_color(tint);
+        } else {
+            draw::set_draw_color(bg);
+        }
+        draw::draw_rectf(x, y, w, h);
+        let font_color = cell.font_color.unwrap_or(if cell.link {
+            opts.link_color
+        } else {
+            opts.cell_font_color
+        });
+        // Locked cells are dimmed while they're being updated
+        draw::set_draw_color(if cell.locked {
+            font_color.inactive()
+        } else {
+            font_color
+        });
+        draw::set_font(
+            if let Some(font) = cell.font {
+                font
+            } else {
+                opts.cell_font
+            },
+            if let Some(font) = cell.font_size {
+                font
+            } else {
+                opts.cell_font_size
+            },
+        );
+        // Typed numbers are right aligned unless told otherwise
+        let align = cell.align.or(props.align).unwrap_or(
+            if cell.value.as_ref().is_some_and(|v| v.is_numeric()) {
+                Align::Right
+            } else {
+                opts.cell_align
+            },
+        );
+        if let Some(CellValue::Bool(checked)) = cell.value {
+            Self::draw_checkbox(checked, x, y, w, h, font_color);
+        } else if props.rtl.unwrap_or(opts.rtl) {
+            Self::draw_text_rtl(
+                &cell.label,
+                x + opts.cell_padding,
+                y,
+                w - opts.cell_padding * 2,
+                h,
+                align,
+            );
+        } else {
+            draw::draw_text2(
+                &cell.label,
+                x + opts.cell_padding,
+                y,
+                w - opts.cell_padding * 2,
+                h,
+                if props.wrap {
+                    align | Align::Wrap
+                } else {
+                    align
+                },
+            );
+            if cell.link && !props.wrap {
+                Self::draw_underline(
+                    &cell.label,
+                    x + opts.cell_padding,
+                    y,
+                    w - opts.cell_padding * 2,
+                    h,
+                    align,
+                );
+            }
+        }
+        draw::set_draw_color(if let Some(col) = cell.border_color {
+            col
+        } else {
+            opts.cell_border_color
+        });
+        for i in 0..opts.cell_border_width {
+            draw::draw_rect(x + i, y + i, w - 2 * i, h - 2 * i);
+        }
+        draw::pop_clip();
+    }
+
+    // Band colors of every row, alternating each time the value in the column changes
+    fn col_bands(data: &CellMatrix, col: i32, colors: &[Color; 2]) -> Vec<Color> {
+        let mut band = 0;
+        let mut prev: Option<&str> = None;
+        data.iter()
+            .map(|r| {
+                let key = r.get(col as usize).map_or("", |c| c.label.as_str());
+                if prev.is_some_and(|p| p != key) {
+                    band ^= 1;
+                }
+                prev = Some(key);
+                colors[band]
+            })
+            .collect()
+    }
+
+    // Draws the watermark centered in the area, shrunk to fit
+    fn draw_watermark(img: &mut fltk::image::RgbImage, x: i32, y: i32, w: i32, h: i32) {
+        let (dw, dh) = (img.data_w(), img.data_h());
+        if dw > w || dh > h {
+            img.scale(w, h, true, false);
+        } else {
+            img.scale(dw, dh, true, true);
+        }
+        draw::push_clip(x, y, w, h);
+        img.draw(
+            x + (w - img.w()) / 2,
+            y + (h - img.h()) / 2,
+            img.w(),
+            img.h(),
+        );
+        draw::pop_clip();
+    }
+
+    // Draws a single line of text right to left within the box, honoring the horizontal alignment
+    fn draw_text_rtl(txt: &str, x: i32, y: i32, w: i32, h: i32, align: Align) {
+        let tw = draw::width(txt) as i32;
+        let right = if align.contains(Align::Left) {
+            x + tw
+        } else if align.contains(Align::Right) {
+            x + w
+        } else {
+            x + (w + tw) / 2
+        };
+        let baseline = y + (h + draw::height()) / 2 - draw::descent();
+        draw::rtl_draw(txt, right, baseline);
+    }
+
+    // Outlines the selected range on the edges bordering unselected cells, and draws the focus rectangle
+    #[allow(clippy::too_many_arguments)]
+    fn draw_selection_decor(
+        t: &table::TableRow,
+        row: i32,
+        col: i32,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        focused: bool,
+        opts: &TableOpts,
+    ) {
+        draw::push_clip(x, y, w, h);
+        let bw = opts.selection_border_width;
+        if bw > 0 {
+            draw::set_draw_color(opts.selection_border_color);
+            if row == 0 || !t.is_selected(row - 1, col) {
+                draw::draw_rectf(x, y, w, bw);
+            }
+            if row + 1 >= t.rows() || !t.is_selected(row + 1, col) {
+                draw::draw_rectf(x, y + h - bw, w, bw);
+            }
+            if col == 0 || !t.is_selected(row, col - 1) {
+                draw::draw_rectf(x, y, bw, h);
+            }
+            if col + 1 >= t.cols() || !t.is_selected(row, col + 1) {
+                draw::draw_rectf(x + w - bw, y, bw, h);
+            }
+        }
+        if let (true, Some(style)) = (focused, opts.focus_line_style) {
+            draw::set_draw_color(opts.focus_color);
+            draw::set_line_style(style, 1);
+            draw::draw_rect(x + bw + 1, y + bw + 1, w - 2 * (bw + 1), h - 2 * (bw + 1));
+            draw::set_line_style(LineStyle::Solid, 0);
+        }
+        draw::pop_clip();
+    }
+
+    /// Set the cell value, using the row and column to index the data.
+    /// The change can be undone with `undo()`
+    pub fn set_cell_value(&mut self, row: i32, col: i32, val: &str) {
+        let old = self.cell_value(row, col);
+        let depth = self.opts.try_lock().unwrap().undo_depth;
+        self.undo_stack
+            .try_lock()
+            .unwrap()
+            .push(row, col, old, val.to_string(), depth);
+        self.store_cell(row, col, val);
+    }
+
+    // Writes the cell into the model or the data, notifying the watchers
+    fn store_cell(&mut self, row: i32, col: i32, val: &str) {
+        if let Some(m) = self.model.try_lock().unwrap().as_mut() {
+            m.set_cell(row, col, val);
+        } else {
+            self.data.try_lock().unwrap()[row as usize][col as usize].set_text(val.to_string());
+        }
+        Self::notify_col_watchers(&self.col_watchers, row, col, val);
+        self.mark_changed();
+    }
+
+    // Records the time of a data change for the debounced change callback
+    // and drops the footer's aggregates computed from the old data
+    fn mark_changed(&self) {
+        *self.changed.lock().unwrap() = Some(Instant::now());
+        self.footer.lock().unwrap().take();
+    }
+
+    /// Call `cb` once the data has stopped changing for `delay`, coalescing bursts of edits
+    /// into one call, e.g. to save the table. Changes made from any thread count,
+    /// but this must be called from the main thread. See `clear_on_data_changed_debounced()`
+    pub fn on_data_changed_debounced<F: FnMut(&mut SmartTable) + Send + 'static>(
+        &mut self,
+        delay: Duration,
+        cb: F,
+    ) {
+        let mut slot = self.on_changed_debounced.try_lock().unwrap();
+        let first = slot.is_none();
+        *slot = Some((delay, Arc::new(Mutex::new(cb))));
+        drop(slot);
+        if !first {
+            // The polling timer is already running
+            return;
+        }
+        const TICK: f64 = 0.05;
+        let mut this = self.clone();
+        app::add_timeout3(TICK, move |handle| {
+            // The timer stops once the callback is cleared or the table deleted
+            let Some((delay, cb)) = this.on_changed_debounced.try_lock().unwrap().clone() else {
+                return;
+            };
+            if this.table.was_deleted() {
+                return;
+            }
+            let mut changed = this.changed.lock().unwrap();
+            if changed.is_some_and(|t| t.elapsed() >= delay) {
+                *changed = None;
+                drop(changed);
+                // Called outside the slot, so it can replace or clear itself
+                cb.try_lock().unwrap()(&mut this);
+            }
+            app::repeat_timeout3(TICK, handle);
+        });
+    }
+
+    /// Remove the callback set with `on_data_changed_debounced()`
+    pub fn clear_on_data_changed_debounced(&mut self) {
+        *self.on_changed_debounced.try_lock().unwrap() = None;
+    }
+
+    // Calls the subscriptions watching the column of the changed cell
+    fn notify_col_watchers(watchers: &Mutex<Vec<ColWatcher>>, row: i32, col: i32, val: &str) {
+        for (cols, cb) in watchers.try_lock().unwrap().iter_mut() {
+            if cols.contains(&col) {
+                cb(row, col, val);
+            }
+        }
+    }
+
+    /// Set a typed cell value, stored as is and displayed as text.
+    /// Typed numbers are right aligned unless the cell or column has an alignment.
+    /// A model is given the value's text. Undoing the change restores the previous text
+    pub fn set_cell_typed<V: Into<CellValue>>(&mut self, row: i32, col: i32, val: V) {
+        let val = val.into();
+        let txt = val.to_string();
+        let old = self.cell_value(row, col);
+        let depth = self.opts.try_lock().unwrap().undo_depth;
+        self.undo_stack
+            .try_lock()
+            .unwrap()
+            .push(row, col, old, txt.clone(), depth);
+        if self.has_model() {
+            self.store_cell(row, col, &txt);
+        } else {
+            self.data.try_lock().unwrap()[row as usize][col as usize].set_value(val);
+            Self::notify_col_watchers(&self.col_watchers, row, col, &txt);
+            self.mark_changed();
+        }
+        self.table.redraw();
+    }
+
+    /// Set a boolean cell, drawn as a checkbox which a click toggles in editable tables
+    pub fn set_cell_checked(&mut self, row: i32, col: i32, checked: bool) {
+        self.set_cell_typed(row, col, checked);
+    }
+
+    /// Returns whether the cell is a checked boolean, or holds the text "true" or "1"
+    pub fn cell_checked(&self, row: i32, col: i32) -> bool {
+        match self.cell_typed(row, col) {
+            CellValue::Bool(b) => b,
+            v => Self::checked_text(&v.to_string()),
+        }
+    }
+
+    // Whether the text of a cell stands for a checked box
+    pub(crate) fn checked_text(text: &str) -> bool {
+        matches!(text.trim(), "true" | "1")
+    }
+
+    /// Get the typed cell value. Cells set from text give `CellValue::Str`,
+    /// or `CellValue::Empty` when empty
+    pub fn cell_typed(&self, row: i32, col: i32) -> CellValue {
+        if let Some(m) = self.model.try_lock().unwrap().as_ref() {
+            return Cell::from_string(m.cell(row, col)).value();
+        }
+        self.data.try_lock().unwrap()[row as usize][col as usize].value()
+    }
+
+    /// Get the cell value, using the row and column to index the data
+    pub fn cell_value(&self, row: i32, col: i32) -> String {
+        if let Some(m) = self.model.try_lock().unwrap().as_ref() {
+            return m.cell(row, col);
+        }
+        self.data.try_lock().unwrap()[row as usize][col as usize]
             .label
             .clone()
     }
 
-    /// Set the cell value, using the row and column to index the data
-    pub fn set_cell_color(&mut self, row: i32, col: i32, color: Color) {
-        self.data.try_lock().unwrap()[row as usize][col as usize].color = Some(color);
+    /// Get the values of a row, locking the data once
+    pub fn row(&self, row: i32) -> Vec<String> {
+        if let Some(m) = self.model.try_lock().unwrap().as_ref() {
+            return (0..m.col_count()).map(|c| m.cell(row, c)).collect();
+        }
+        self.data.try_lock().unwrap()[row as usize]
+            .iter()
+            .map(|c| c.label.clone())
+            .collect()
+    }
+
+    /// Get the values of a column, locking the data once
+    pub fn col(&self, col: i32) -> Vec<String> {
+        if let Some(m) = self.model.try_lock().unwrap().as_ref() {
+            return (0..m.row_count()).map(|r| m.cell(r, col)).collect();
+        }
+        self.data
+            .try_lock()
+            .unwrap()
+            .iter()
+            .map(|r| r[col as usize].label.clone())
+            .collect()
+    }
+
+    /// Iterate over the rows as (row index, values), from a copy of the data taken under one lock.
+    /// Named so as not to shadow `TableExt::rows()`
+    pub fn iter_rows(&self) -> impl Iterator<Item = (i32, Vec<String>)> {
+        let rows: Vec<Vec<String>> = match self.model.try_lock().unwrap().as_ref() {
+            Some(m) => (0..m.row_count())
+                .map(|r| (0..m.col_count()).map(|c| m.cell(r, c)).collect())
+                .collect(),
+            None => self
+                .data
+                .try_lock()
+                .unwrap()
+                .iter()
+                .map(|r| r.iter().map(|c| c.label.clone()).collect())
+                .collect(),
+        };
+        rows.into_iter().enumerate().map(|(i, r)| (i as i32, r))
+    }
+
+    /// Iterate over the cells row by row as (row, column, value), see `iter_rows()`
+    pub fn iter_cells(&self) -> impl Iterator<Item = (i32, i32, String)> {
+        self.iter_rows().flat_map(|(row, vals)| {
+            vals.into_iter()
+                .enumerate()
+                .map(move |(col, v)| (row, col as i32, v))
+        })
+    }
+
+    /// Set the cell value, using the row and column to index the data
+    pub fn set_cell_color(&mut self, row: i32, col: i32, color: Color) {
+        self.style_cell(row, col, |cell| cell.color = Some(color));
+    }
+
+    /// Set the cell value, using the row and column to index the data
+    pub fn set_cell_selection_color(&mut self, row: i32, col: i32, color: Color) {
+        self.style_cell(row, col, |cell| cell.selection_color = Some(color));
+    }
+
+    /// Set the cell value, using the row and column to index the data
+    pub fn set_cell_font_color(&mut self, row: i32, col: i32, color: Color) {
+        self.style_cell(row, col, |cell| cell.font_color = Some(color));
+    }
+
+    /// Set the cell value, using the row and column to index the data
+    pub fn set_cell_border_color(&mut self, row: i32, col: i32, color: Color) {
+        self.style_cell(row, col, |cell| cell.border_color = Some(color));
+    }
+
+    /// Set the cell value, using the row and column to index the data
+    pub fn set_cell_font(&mut self, row: i32, col: i32, font: Font) {
+        self.style_cell(row, col, |cell| cell.font = Some(font));
+    }
+
+    /// Set the cell value, using the row and column to index the data
+    pub fn set_cell_font_size(&mut self, row: i32, col: i32, sz: i32) {
+        self.style_cell(row, col, |cell| cell.font_size = Some(sz));
+    }
+
+    /// Set the cell value, using the row and column to index the data
+    pub fn set_cell_align(&mut self, row: i32, col: i32, align: Align) {
+        self.style_cell(row, col, |cell| cell.align = Some(align));
+    }
+
+    // Styles the stored cell, which a model doesn't have
+    fn style_cell(&mut self, row: i32, col: i32, f: impl FnOnce(&mut Cell)) {
+        if self.has_model() {
+            return;
+        }
+        f(&mut self.data.try_lock().unwrap()[row as usize][col as usize]);
+    }
+
+    // Changes the styling options in place. Unlike `set_opts()`, the styling setters below
+    // leave the data and headers as they are
+    fn restyle(&mut self, f: impl FnOnce(&mut TableOpts)) {
+        f(&mut self.opts.try_lock().unwrap());
+        self.table.redraw();
+    }
+
+    /// Set the default background color of the cells
+    pub fn set_default_cell_color(&mut self, v: Color) {
+        self.restyle(|o| o.cell_color = v);
+    }
+
+    /// Set the default font of the cells
+    pub fn set_default_cell_font(&mut self, v: Font) {
+        self.restyle(|o| o.cell_font = v);
+    }
+
+    /// Set the default text color of the cells
+    pub fn set_default_cell_font_color(&mut self, v: Color) {
+        self.restyle(|o| o.cell_font_color = v);
+    }
+
+    /// Set the default font size of the cells
+    pub fn set_default_cell_font_size(&mut self, v: i32) {
+        self.restyle(|o| o.cell_font_size = v);
+    }
+
+    /// Set the default alignment of the cells
+    pub fn set_default_cell_align(&mut self, v: Align) {
+        self.restyle(|o| o.cell_align = v);
+    }
+
+    /// Set the default background color of the selected cells
+    pub fn set_default_cell_selection_color(&mut self, v: Color) {
+        self.restyle(|o| o.cell_selection_color = v);
+    }
+
+    /// Set the default border color of the cells
+    pub fn set_default_cell_border_color(&mut self, v: Color) {
+        self.restyle(|o| o.cell_border_color = v);
+    }
+
+    /// Set the background color of the headers
+    pub fn set_header_color(&mut self, v: Color) {
+        self.restyle(|o| o.header_color = v);
+    }
+
+    /// Set the font of the headers
+    pub fn set_header_font(&mut self, v: Font) {
+        self.restyle(|o| o.header_font = v);
+    }
+
+    /// Set the text color of the headers
+    pub fn set_header_font_color(&mut self, v: Color) {
+        self.restyle(|o| o.header_font_color = v);
+    }
+
+    /// Set the font size of the headers
+    pub fn set_header_font_size(&mut self, v: i32) {
+        self.restyle(|o| o.header_font_size = v);
+    }
+
+    /// Set the alignment of the headers
+    pub fn set_header_align(&mut self, v: Align) {
+        self.restyle(|o| o.header_align = v);
+    }
+
+    /// Set the styling of the cell, replacing the overrides set on it before.
+    /// Does nothing while a model is set
+    pub fn set_cell_style(&mut self, row: i32, col: i32, style: CellStyle) {
+        if self.has_model() {
+            return;
+        }
+        let cell = &mut self.data.try_lock().unwrap()[row as usize][col as usize];
+        cell.color = style.color;
+        cell.font = style.font;
+        cell.font_color = style.font_color;
+        cell.font_size = style.font_size;
+        cell.align = style.align;
+        cell.selection_color = style.selection_color;
+        cell.border_color = style.border_color;
+        self.table.redraw();
+    }
+
+    /// Get the styling overrides of the cell
+    pub fn cell_style(&self, row: i32, col: i32) -> CellStyle {
+        if self.has_model() {
+            return CellStyle::default();
+        }
+        let cell = &self.data.try_lock().unwrap()[row as usize][col as usize];
+        CellStyle {
+            color: cell.color,
+            font: cell.font,
+            font_color: cell.font_color,
+            font_size: cell.font_size,
+            align: cell.align,
+            selection_color: cell.selection_color,
+            border_color: cell.border_color,
+        }
+    }
+
+    /// Replace all the data at once, resizing the table to fit the new matrix.
+    /// Existing headers are kept where their index still exists, new rows and columns get default headers.
+    /// The data stays locked during the swap so no intermediate state is drawn, the scroll position
+    /// and selection are restored where possible, and the table is redrawn once.
+    /// Does nothing while a model is set
+    pub fn replace_data_frozen(&mut self, new_data: StringMatrix) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        let rows = new_data.len();
+        let cols = new_data.iter().map(|r| r.len()).max().unwrap_or(0);
+        *data = new_data
+            .into_iter()
+            .map(|r| {
+                let mut r: Vec<Cell> = r.iter().map(|v| Cell::with_label(v)).collect();
+                r.resize(cols, Cell::default());
+                r
+            })
+            .collect();
+
+        let mut row_headers = self.row_headers.try_lock().unwrap();
+        let old_rows = row_headers.len();
+        row_headers.truncate(rows);
+        row_headers.extend((old_rows..rows).map(|i| self.row_label(i as _)));
+        let mut col_headers = self.col_headers.try_lock().unwrap();
+        let old_cols = col_headers.len();
+        col_headers.truncate(cols);
+        col_headers.extend((old_cols..cols).map(|i| self.col_label(i as _)));
+        self.row_tags.try_lock().unwrap().resize(rows, None);
+        self.tree.try_lock().unwrap().resize(rows, None);
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .resize(cols, ColProps::default());
+
+        let (rows, cols) = (rows as i32, cols as i32);
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let (row_pos, col_pos) = (self.table.row_position(), self.table.col_position());
+        let sel = self
+            .table
+            .try_get_selection()
+            .filter(|s| s.0 >= 0 && s.1 >= 0);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        self.table.set_cols(cols + rtl as i32);
+        if rows > 0 {
+            self.table.set_row_position(row_pos.min(rows - 1));
+        }
+        if cols > 0 {
+            self.table
+                .set_col_position(col_pos.min(cols - 1 + rtl as i32));
+        }
+        if sel.is_some() {
+            Self::clamp_selection(&mut self.table);
+        } else {
+            self.table.unset_selection();
+        }
+        drop(data);
+        self.table.redraw();
+    }
+
+    /// Replace all the data at once, see `replace_data_frozen()`
+    pub fn set_data(&mut self, data: StringMatrix) {
+        self.replace_data_frozen(data);
+    }
+
+    /// Replace the values of a row with one `update()`, undone as one step.
+    /// Values past the last column are ignored, cells past the last value are left as is.
+    /// Like `set_cell_value()`, locked cells are written too
+    pub fn set_row(&mut self, row: i32, vals: &[&str]) {
+        self.update(|b| {
+            if (0..b.row_count()).contains(&row) {
+                for (col, v) in (0..b.column_count()).zip(vals) {
+                    b.set_cell_value(row, col, v);
+                }
+            }
+        });
+    }
+
+    /// Replace the values of a column with one `update()`, undone as one step.
+    /// Values past the last row are ignored, cells past the last value are left as is.
+    /// Like `set_cell_value()`, locked cells are written too
+    pub fn set_col(&mut self, col: i32, vals: &[&str]) {
+        self.update(|b| {
+            if (0..b.column_count()).contains(&col) {
+                for (row, v) in (0..b.row_count()).zip(vals) {
+                    b.set_cell_value(row, col, v);
+                }
+            }
+        });
+    }
+
+    /// Parse the column's values as numbers, cells which aren't numeric give `None`
+    pub fn col_as_f64(&self, col: i32) -> Vec<Option<f64>> {
+        self.with_col_cells(col, |cells| cells.map(|c| c.as_f64()).collect())
+    }
+
+    // Passes the cells of the column, of the model or of the data, in data order
+    pub(crate) fn with_col_cells<R>(
+        &self,
+        col: i32,
+        f: impl FnOnce(&mut dyn Iterator<Item = Cow<Cell>>) -> R,
+    ) -> R {
+        let data = self.data.try_lock().unwrap();
+        let model = self.model.try_lock().unwrap();
+        let model = model.as_deref();
+        let (rows, _) = Self::source_size(&data, model);
+        f(&mut (0..rows).filter_map(|r| Self::source_cell(&data, model, r, col)))
+    }
+
+    /// Returns the sum of the column's numeric cells, the others are skipped
+    pub fn col_sum(&self, col: i32) -> f64 {
+        self.fold_col_f64(col, 0.0, |sum, v| sum + v)
+    }
+
+    /// Returns the smallest of the column's numeric cells, `None` without any
+    pub fn col_min(&self, col: i32) -> Option<f64> {
+        self.fold_col_f64(col, None, |min, v| Some(v.min(min.unwrap_or(v))))
+    }
+
+    /// Returns the largest of the column's numeric cells, `None` without any
+    pub fn col_max(&self, col: i32) -> Option<f64> {
+        self.fold_col_f64(col, None, |max, v| Some(v.max(max.unwrap_or(v))))
+    }
+
+    /// Returns the average of the column's numeric cells, `None` without any
+    pub fn col_avg(&self, col: i32) -> Option<f64> {
+        let (n, sum) = self.fold_col_f64(col, (0, 0.0), |(n, sum), v| (n + 1, sum + v));
+        (n > 0).then(|| sum / n as f64)
+    }
+
+    /// Fold the text of the column's cells into a value, starting from `init`,
+    /// e.g. to count the cells matching a status without copying the data out
+    pub fn aggregate<A>(&self, col: i32, init: A, mut f: impl FnMut(&str, A) -> A) -> A {
+        self.with_col_cells(col, |cells| cells.fold(init, |acc, c| f(&c.label, acc)))
+    }
+
+    // Folds the numbers of the column's numeric cells
+    fn fold_col_f64<A>(&self, col: i32, init: A, f: impl FnMut(A, f64) -> A) -> A {
+        self.with_col_cells(col, |cells| cells.filter_map(|c| c.as_f64()).fold(init, f))
+    }
+
+    /// Parse the selected cells as numbers, giving one series per selected column
+    /// named by its column header. Empty without a selection
+    pub fn selection_as_series(&self) -> Vec<(String, Vec<Option<f64>>)> {
+        let Some((r1, c1, r2, c2)) = self
+            .table
+            .try_get_selection()
+            .filter(|s| s.0 >= 0 && s.1 >= 0)
+        else {
+            return vec![];
+        };
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let ncols = self.column_count();
+        let mut cols: Vec<i32> = (c1..=c2)
+            .filter_map(|c| Self::data_col(rtl, ncols, c))
+            .collect();
+        cols.sort_unstable();
+        let rows: Vec<i32> = (r1..=r2).map(|r| self.view_to_data_row(r)).collect();
+        let data = self.data.try_lock().unwrap();
+        let model = self.model.try_lock().unwrap();
+        let model = model.as_deref();
+        let col_headers = self.col_headers.try_lock().unwrap();
+        cols.iter()
+            .map(|c| {
+                let values = rows
+                    .iter()
+                    .map(|r| Self::source_cell(&data, model, *r, *c).and_then(|c| c.as_f64()))
+                    .collect();
+                let header = match model {
+                    Some(m) => m.col_header(*c),
+                    None => col_headers[*c as usize].clone(),
+                };
+                (header, values)
+            })
+            .collect()
+    }
+
+    /// Subscribe to changes of the cells in the given columns, the callback gets the row, column and
+    /// new value of each cell changed by the editor or `set_cell_value()`
+    pub fn subscribe_cols<F: FnMut(i32, i32, &str) + Send + 'static>(
+        &mut self,
+        cols: &[i32],
+        cb: F,
+    ) {
+        self.col_watchers
+            .try_lock()
+            .unwrap()
+            .push((cols.to_vec(), Box::new(cb)));
+    }
+
+    /// Remove all column subscriptions
+    pub fn clear_col_subscriptions(&mut self) {
+        self.col_watchers.try_lock().unwrap().clear();
+    }
+
+    /// Take a snapshot of the data, headers and dimensions, to be restored with `rollback()`
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            data: self.data.try_lock().unwrap().clone(),
+            row_headers: self.row_headers.try_lock().unwrap().clone(),
+            col_headers: self.col_headers.try_lock().unwrap().clone(),
+            row_tags: self.row_tags.try_lock().unwrap().clone(),
+            tree: self.tree.try_lock().unwrap().clone(),
+            col_props: self.col_props.try_lock().unwrap().clone(),
+        }
+    }
+
+    /// Restore the table to the state it was in when the checkpoint was taken,
+    /// reverting every change made since
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        let cols = checkpoint.col_headers.len() as i32;
+        *data = checkpoint.data;
+        *self.row_headers.try_lock().unwrap() = checkpoint.row_headers;
+        *self.col_headers.try_lock().unwrap() = checkpoint.col_headers;
+        *self.row_tags.try_lock().unwrap() = checkpoint.row_tags;
+        *self.tree.try_lock().unwrap() = checkpoint.tree;
+        *self.col_props.try_lock().unwrap() = checkpoint.col_props;
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        self.table.unset_selection();
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        self.table.set_cols(cols + rtl as i32);
+        drop(data);
+        self.table.redraw();
+    }
+
+    /// Set a function giving each row a background color from its index and cells,
+    /// `None` leaves the row with the default cell color. Cells with their own color keep it
+    pub fn set_row_band_fn<F: Fn(i32, &[Cell]) -> Option<Color> + Send + 'static>(&mut self, f: F) {
+        *self.row_band.try_lock().unwrap() = Some(RowBand::Fn(Box::new(f)));
+        self.table.redraw();
+    }
+
+    /// Group contiguous rows sharing the same value in the column,
+    /// alternating between the two colors from one group to the next
+    pub fn set_row_band_col(&mut self, col: i32, colors: [Color; 2]) {
+        *self.row_band.try_lock().unwrap() = Some(RowBand::Col(col, colors));
+        self.table.redraw();
+    }
+
+    /// Remove the row banding
+    pub fn clear_row_band(&mut self) {
+        *self.row_band.try_lock().unwrap() = None;
+        self.table.redraw();
+    }
+
+    /// Render the selected cells, with their styling, into an image.
+    /// fltk-rs can only put text on the clipboard, so pass the image to a clipboard crate
+    /// (e.g. arboard) to paste it into other applications. Returns `None` without a selection
+    pub fn selection_image(&self) -> Option<fltk::image::RgbImage> {
+        use fltk::{prelude::SurfaceDevice, surface::ImageSurface};
+        let (r1, c1, r2, c2) = self
+            .table
+            .try_get_selection()
+            .filter(|s| s.0 >= 0 && s.1 >= 0)?;
+        let mut opts = *self.opts.try_lock().unwrap();
+        if opts.theme_colors {
+            opts = opts.themed();
+        }
+        let ncols = self.column_count();
+        let cols: Vec<(i32, i32)> = (c1..=c2)
+            .filter_map(|c| Self::data_col(opts.rtl, ncols, c).map(|d| (c, d)))
+            .collect();
+        let w: i32 = cols.iter().map(|(c, _)| self.table.col_width(*c)).sum();
+        let h: i32 = (r1..=r2).map(|r| self.table.row_height(r)).sum();
+        if w <= 0 || h <= 0 {
+            return None;
+        }
+        let filter = self.filter.try_lock().unwrap();
+        let data = self.data.try_lock().unwrap();
+        let model = self.model.try_lock().unwrap();
+        let model = model.as_deref();
+        let col_props = self.col_props.try_lock().unwrap();
+        let row_band = self.row_band.try_lock().unwrap();
+        let bands = match &*row_band {
+            Some(RowBand::Col(col, colors)) => Self::col_bands(&data, *col, colors),
+            _ => vec![],
+        };
+        let surf = ImageSurface::new(w, h, false);
+        ImageSurface::push_current(&surf);
+        let mut y = 0;
+        for row in r1..=r2 {
+            let rh = self.table.row_height(row);
+            let row = Self::data_row(&filter, row);
+            let band = match &*row_band {
+                Some(RowBand::Fn(f)) => data.get(row as usize).and_then(|r| f(row, r)),
+                _ => bands.get(row as usize).copied(),
+            };
+            let mut x = 0;
+            for (c, d) in &cols {
+                let cw = self.table.col_width(*c);
+                let cell = Self::source_cell(&data, model, row, *d).unwrap_or_default();
+                Self::draw_data(
+                    &cell,
+                    &col_props[*d as usize],
+                    x,
+                    y,
+                    cw,
+                    rh,
+                    false,
+                    false,
+                    band,
+                    &opts,
+                );
+                x += cw;
+            }
+            y += rh;
+        }
+        ImageSurface::pop_current();
+        surf.image()
+    }
+
+    /// Mark a cell as being updated in the background. The inline editor won't open on a locked
+    /// cell, and an edit already in progress can't be committed until the cell is unlocked.
+    /// Does nothing while a model is set
+    pub fn lock_cell(&mut self, row: i32, col: i32) {
+        if self.has_model() {
+            return;
+        }
+        self.data.try_lock().unwrap()[row as usize][col as usize].locked = true;
+        self.table.redraw();
+    }
+
+    /// Unlock a cell previously locked with [`SmartTable::lock_cell`]
+    pub fn unlock_cell(&mut self, row: i32, col: i32) {
+        if self.has_model() {
+            return;
+        }
+        self.data.try_lock().unwrap()[row as usize][col as usize].locked = false;
+        self.table.redraw();
+    }
+
+    /// Lock all the cells of a row, see [`SmartTable::lock_cell`]
+    pub fn lock_row(&mut self, row: i32) {
+        if self.has_model() {
+            return;
+        }
+        for c in self.data.try_lock().unwrap()[row as usize].iter_mut() {
+            c.locked = true;
+        }
+        self.table.redraw();
+    }
+
+    /// Unlock all the cells of a row
+    pub fn unlock_row(&mut self, row: i32) {
+        if self.has_model() {
+            return;
+        }
+        for c in self.data.try_lock().unwrap()[row as usize].iter_mut() {
+            c.locked = false;
+        }
+        self.table.redraw();
+    }
+
+    /// Returns whether a cell is locked
+    pub fn is_cell_locked(&self, row: i32, col: i32) -> bool {
+        !self.has_model() && self.data.try_lock().unwrap()[row as usize][col as usize].locked
+    }
+
+    /// Set the row header value at the row index
+    pub fn set_row_header_value(&mut self, row: i32, val: &str) {
+        if self.has_model() {
+            return;
+        }
+        self.row_headers.try_lock().unwrap()[row as usize] = val.to_string();
+    }
+
+    /// Set a tooltip shown when hovering the column header, e.g. to give its units or source
+    pub fn set_col_header_tooltip(&mut self, col: i32, text: &str) {
+        self.col_props.try_lock().unwrap()[col as usize].tooltip = Some(text.to_string());
+    }
+
+    /// Set the column header value at the column index
+    pub fn set_col_header_value(&mut self, col: i32, val: &str) {
+        self.col_headers.try_lock().unwrap()[col as usize] = val.to_string();
+    }
+
+    /// Replace all the row headers, or return an error when there isn't one per row
+    pub fn set_row_headers(&mut self, headers: &[&str]) -> Result<(), TableError> {
+        let mut row_headers = self.row_headers.try_lock().unwrap();
+        Self::check_len(row_headers.len() as i32, headers.len())?;
+        *row_headers = headers.iter().map(|h| h.to_string()).collect();
+        drop(row_headers);
+        self.table.redraw();
+        Ok(())
+    }
+
+    /// Replace all the column headers, or return an error when there isn't one per column
+    pub fn set_col_headers(&mut self, headers: &[&str]) -> Result<(), TableError> {
+        let mut col_headers = self.col_headers.try_lock().unwrap();
+        Self::check_len(col_headers.len() as i32, headers.len())?;
+        *col_headers = headers.iter().map(|h| h.to_string()).collect();
+        drop(col_headers);
+        self.table.redraw();
+        Ok(())
+    }
+
+    /// Make the default header of a row from its index, e.g. a date, instead of the row number.
+    /// The current row headers are replaced by the new defaults
+    pub fn set_row_header_label<F: Fn(i32) -> String + Send + 'static>(&mut self, f: F) {
+        *self.row_header_label.try_lock().unwrap() = Some(Box::new(f));
+        let rows = self.row_headers.try_lock().unwrap().len() as i32;
+        *self.row_headers.try_lock().unwrap() = (0..rows).map(|i| self.row_label(i)).collect();
+        self.table.redraw();
+    }
+
+    /// Make the default header of a column from its index, e.g. a month name, instead of the
+    /// spreadsheet-like A, B, ... Z, AA, AB... The current column headers are replaced by the
+    /// new defaults
+    pub fn set_col_header_label<F: Fn(i32) -> String + Send + 'static>(&mut self, f: F) {
+        *self.col_header_label.try_lock().unwrap() = Some(Box::new(f));
+        let cols = self.col_headers.try_lock().unwrap().len() as i32;
+        *self.col_headers.try_lock().unwrap() = (0..cols).map(|i| self.col_label(i)).collect();
+        self.table.redraw();
+    }
+
+    /// Get the row header value at the row index
+    pub fn row_header_value(&mut self, row: i32) -> String {
+        if let Some(m) = self.model.try_lock().unwrap().as_ref() {
+            return m.row_header(row);
+        }
+        self.row_headers.try_lock().unwrap()[row as usize].clone()
+    }
+
+    /// Get the column header value at the column index
+    pub fn col_header_value(&mut self, col: i32) -> String {
+        self.col_headers.try_lock().unwrap()[col as usize].clone()
+    }
+
+    /// Attach a tag to the row at the row index.
+    /// The tag follows its row when rows are inserted or removed around it
+    pub fn set_row_tag<T: Any + Send + Sync>(&mut self, row: i32, tag: T) {
+        if self.has_model() {
+            return;
+        }
+        self.row_tags.try_lock().unwrap()[row as usize] = Some(Arc::new(tag));
+    }
+
+    /// Get a copy of the row's tag, if one of type `T` was set
+    pub fn row_tag<T: Any + Clone>(&self, row: i32) -> Option<T> {
+        if self.has_model() {
+            return None;
+        }
+        self.row_tags.try_lock().unwrap()[row as usize]
+            .as_ref()
+            .and_then(|tag| tag.downcast_ref::<T>().cloned())
+    }
+
+    /// Get the row's raw tag
+    pub fn row_tag_ref(&self, row: i32) -> Option<RowTag> {
+        if self.has_model() {
+            return None;
+        }
+        self.row_tags.try_lock().unwrap()[row as usize].clone()
+    }
+
+    /// Remove the row's tag
+    pub fn clear_row_tag(&mut self, row: i32) {
+        if self.has_model() {
+            return;
+        }
+        self.row_tags.try_lock().unwrap()[row as usize] = None;
+    }
+
+    /// Insert an empty row at the row index
+    pub fn insert_empty_row(&mut self, row: i32, row_header: &str) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        let cols = self.column_count() as usize;
+        data.insert(row as _, vec![]);
+        data[row as usize].resize(cols as _, Cell::default());
+        self.row_headers
+            .try_lock()
+            .unwrap()
+            .insert(row as _, row_header.to_string());
+        self.row_tags.try_lock().unwrap().insert(row as _, None);
+        self.tree.try_lock().unwrap().insert(row as _, None);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+    }
+
+    /// Insert a row in your table.
+    /// Panics if the number of values doesn't match the number of columns, see `try_insert_row()`
+    pub fn insert_row(&mut self, row: i32, row_header: &str, vals: &[&str]) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        let cols = self.column_count() as usize;
+        assert!(cols == vals.len());
+        data.insert(row as _, vals.iter().map(|v| Cell::with_label(v)).collect());
+        self.row_headers
+            .try_lock()
+            .unwrap()
+            .insert(row as _, row_header.to_string());
+        self.row_tags.try_lock().unwrap().insert(row as _, None);
+        self.tree.try_lock().unwrap().insert(row as _, None);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+    }
+
+    /// Append an empty row to your table
+    pub fn append_empty_row(&mut self, row_header: &str) {
+        if self.has_model() {
+            return;
+        }
+        self.notify_limit(TableLimit::Rows, 1);
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        if self.fit_rows(&mut data, 1).is_empty() {
+            return;
+        }
+        let cols = self.column_count() as usize;
+        data.push(vec![]);
+        data.last_mut().unwrap().resize(cols as _, Cell::default());
+        self.row_headers
+            .try_lock()
+            .unwrap()
+            .push(row_header.to_string());
+        self.row_tags.try_lock().unwrap().push(None);
+        self.tree.try_lock().unwrap().push(None);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        self.table.redraw();
+    }
+
+    /// Append a row to your table.
+    /// Panics if the number of values doesn't match the number of columns, see `try_append_row()`
+    pub fn append_row(&mut self, row_header: &str, vals: &[&str]) {
+        if self.has_model() {
+            return;
+        }
+        self.notify_limit(TableLimit::Rows, 1);
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        let cols = self.column_count() as usize;
+        assert!(cols == vals.len());
+        if self.fit_rows(&mut data, 1).is_empty() {
+            return;
+        }
+        data.push(vals.iter().map(|v| Cell::with_label(v)).collect());
+        self.row_headers
+            .try_lock()
+            .unwrap()
+            .push(row_header.to_string());
+        self.row_tags.try_lock().unwrap().push(None);
+        self.tree.try_lock().unwrap().push(None);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        self.table.redraw();
+    }
+
+    /// Append every row from the iterator, with the row numbers as headers.
+    /// Short rows are padded with empty cells and the table widens to fit long ones.
+    /// The data is locked once and the table resized and redrawn once
+    pub fn extend_rows<I, R>(&mut self, rows: I)
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator,
+        R::Item: ToString,
+    {
+        if self.has_model() {
+            return;
+        }
+        let mut new_rows: CellMatrix = rows
+            .into_iter()
+            .map(|r| {
+                r.into_iter()
+                    .map(|v| Cell::from_string(v.to_string()))
+                    .collect()
+            })
+            .collect();
+        self.notify_limit(TableLimit::Rows, new_rows.len());
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        let keep = self.fit_rows(&mut data, new_rows.len());
+        let old_rows = data.len();
+        let mut cols = self.column_count() as usize;
+        data.extend(new_rows.drain(keep));
+        let widest = data[old_rows..].iter().map(|r| r.len()).max().unwrap_or(0);
+        if widest > cols {
+            let mut col_headers = self.col_headers.try_lock().unwrap();
+            col_headers.extend((cols..widest).map(|i| self.col_label(i as _)));
+            self.col_props
+                .try_lock()
+                .unwrap()
+                .resize(widest, ColProps::default());
+            let rtl = self.opts.try_lock().unwrap().rtl;
+            self.table.set_cols(widest as i32 + rtl as i32);
+            cols = widest;
+        }
+        for r in data.iter_mut() {
+            r.resize(cols, Cell::default());
+        }
+        let rows = data.len();
+        self.row_headers
+            .try_lock()
+            .unwrap()
+            .extend((old_rows..rows).map(|i| self.row_label(i as _)));
+        self.row_tags.try_lock().unwrap().resize(rows, None);
+        self.tree.try_lock().unwrap().resize(rows, None);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.table.redraw();
+    }
+
+    /// Append the rows, see `extend_rows()`
+    pub fn append_rows(&mut self, rows: &[Vec<String>]) {
+        self.extend_rows(rows);
+    }
+
+    /// Insert the rows at the index, with their position as headers.
+    /// Short rows are padded with empty cells and the table widens to fit long ones.
+    /// The data is locked once and the table resized and redrawn once
+    pub fn insert_rows(&mut self, at: i32, rows: &[Vec<String>]) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        let at = (at.max(0) as usize).min(data.len());
+        let mut cols = self.column_count() as usize;
+        let widest = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        if widest > cols {
+            let mut col_headers = self.col_headers.try_lock().unwrap();
+            col_headers.extend((cols..widest).map(|i| self.col_label(i as _)));
+            self.col_props
+                .try_lock()
+                .unwrap()
+                .resize(widest, ColProps::default());
+            let rtl = self.opts.try_lock().unwrap().rtl;
+            self.table.set_cols(widest as i32 + rtl as i32);
+            cols = widest;
+        }
+        data.splice(
+            at..at,
+            rows.iter()
+                .map(|r| r.iter().map(|v| Cell::with_label(v)).collect()),
+        );
+        for r in data.iter_mut() {
+            r.resize(cols, Cell::default());
+        }
+        self.row_headers.try_lock().unwrap().splice(
+            at..at,
+            (at..at + rows.len()).map(|i| self.row_label(i as _)),
+        );
+        self.row_tags
+            .try_lock()
+            .unwrap()
+            .splice(at..at, std::iter::repeat_n(None, rows.len()));
+        self.tree
+            .try_lock()
+            .unwrap()
+            .splice(at..at, std::iter::repeat_n(None, rows.len()));
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.table.redraw();
+    }
+
+    /// Start building a row to append, e.g. `table.new_row().header("R10").cell("a").cell(2).commit()`
+    pub fn new_row(&mut self) -> RowBuilder<'_> {
+        RowBuilder {
+            table: self,
+            header: None,
+            cells: vec![],
+        }
+    }
+
+    /// Insert an empty column at the column index
+    pub fn insert_empty_col(&mut self, col: i32, col_header: &str) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        for v in data.iter_mut() {
+            v.insert(col as _, Cell::default());
+        }
+        self.col_headers
+            .try_lock()
+            .unwrap()
+            .insert(col as _, col_header.to_string());
+        drop(data);
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .insert(col as _, ColProps::default());
+        self.table.set_cols(self.table.cols() + 1);
+        self.remap_cols(|c| Some(if c >= col { c + 1 } else { c }));
+    }
+
+    /// Insert a column in your table.
+    /// Panics if the number of values doesn't match the number of rows, see `try_insert_col()`
+    pub fn insert_col(&mut self, col: i32, col_header: &str, vals: &[&str]) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        assert!(vals.len() == data.len());
+        for (count, v) in data.iter_mut().enumerate() {
+            v.insert(col as _, Cell::with_label(vals[count]));
+        }
+        self.col_headers
+            .try_lock()
+            .unwrap()
+            .insert(col as _, col_header.to_string());
+        drop(data);
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .insert(col as _, ColProps::default());
+        self.table.set_cols(self.table.cols() + 1);
+        self.remap_cols(|c| Some(if c >= col { c + 1 } else { c }));
+    }
+
+    /// Append an empty column to your table
+    pub fn append_empty_col(&mut self, col_header: &str) {
+        if self.has_model() {
+            return;
+        }
+        self.notify_limit(TableLimit::Cols, 1);
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        if self.fit_cols(&mut data, 1).is_empty() {
+            return;
+        }
+        for v in data.iter_mut() {
+            v.push(Cell::default());
+        }
+        self.col_headers
+            .try_lock()
+            .unwrap()
+            .push(col_header.to_string());
+        self.col_props.try_lock().unwrap().push(ColProps::default());
+        drop(data);
+        self.sync_cols();
+    }
+
+    /// Append a column to your table.
+    /// Panics if the number of values doesn't match the number of rows, see `try_append_col()`
+    pub fn append_col(&mut self, col_header: &str, vals: &[&str]) {
+        if self.has_model() {
+            return;
+        }
+        self.notify_limit(TableLimit::Cols, 1);
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        assert!(vals.len() == data.len());
+        if self.fit_cols(&mut data, 1).is_empty() {
+            return;
+        }
+        for (count, v) in data.iter_mut().enumerate() {
+            v.push(Cell::with_label(vals[count]));
+        }
+        self.col_headers
+            .try_lock()
+            .unwrap()
+            .push(col_header.to_string());
+        self.col_props.try_lock().unwrap().push(ColProps::default());
+        drop(data);
+        self.sync_cols();
+    }
+
+    // Sets the column count of the inner table from the column headers
+    fn sync_cols(&mut self) {
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let cols = self.col_headers.try_lock().unwrap().len() as i32;
+        self.table.set_cols(cols + rtl as i32);
+        self.table.redraw();
+    }
+
+    /// Change the number of rows and columns without resetting the table like `set_opts()`.
+    /// The cells, headers and column settings which still exist are kept, new rows and columns
+    /// are empty with default headers
+    pub fn set_dimensions(&mut self, rows: i32, cols: i32) {
+        if self.has_model() {
+            return;
+        }
+        let (rows, cols) = (rows.max(0), cols.max(0));
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        data.resize(rows as _, vec![]);
+        for v in data.iter_mut() {
+            v.resize(cols as _, Cell::default());
+        }
+        let mut row_headers = self.row_headers.try_lock().unwrap();
+        row_headers.truncate(rows as _);
+        for i in row_headers.len() as i32..rows {
+            row_headers.push(self.row_label(i));
+        }
+        drop(row_headers);
+        let mut col_headers = self.col_headers.try_lock().unwrap();
+        col_headers.truncate(cols as _);
+        for i in col_headers.len() as i32..cols {
+            col_headers.push(self.col_label(i));
+        }
+        drop(col_headers);
+        self.row_tags.try_lock().unwrap().resize(rows as _, None);
+        self.tree.try_lock().unwrap().resize(rows as _, None);
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .resize(cols as _, ColProps::default());
+        let mut opts = self.opts.try_lock().unwrap();
+        opts.rows = rows;
+        opts.cols = cols;
+        drop(opts);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.sync_cols();
+        Self::clamp_selection(&mut self.table);
+    }
+
+    /// Cap the number of rows and columns, `None` leaves it unlimited. Appends past a cap
+    /// are handled by the policy. Inserts and data replacements aren't limited
+    pub fn set_limits(
+        &mut self,
+        max_rows: Option<usize>,
+        max_cols: Option<usize>,
+        policy: OverflowPolicy,
+    ) {
+        *self.limits.try_lock().unwrap() = Limits {
+            max_rows,
+            max_cols,
+            policy,
+        };
+    }
+
+    /// Set a callback called whenever an append hits a limit, before the policy is applied
+    pub fn set_on_limit_reached<F: FnMut(TableLimit) + Send + 'static>(&mut self, cb: F) {
+        *self.on_limit_reached.try_lock().unwrap() = Some(Box::new(cb));
+    }
+
+    // Calls the on_limit_reached callback if appending `incoming` rows or columns goes past
+    // the limit. Appends call it before locking the data, so the callback can use the table
+    fn notify_limit(&self, limit: TableLimit, incoming: usize) {
+        let limits = *self.limits.try_lock().unwrap();
+        let (max, count) = match limit {
+            TableLimit::Rows => (limits.max_rows, self.data.try_lock().unwrap().len()),
+            TableLimit::Cols => (limits.max_cols, self.col_headers.try_lock().unwrap().len()),
+        };
+        if max.is_some_and(|max| count + incoming > max) {
+            self.call_limit_cb(limit);
+        }
+    }
+
+    // Calls the on_limit_reached callback out of its slot, so it can replace itself
+    fn call_limit_cb(&self, limit: TableLimit) {
+        let cb = self.on_limit_reached.try_lock().unwrap().take();
+        if let Some(mut cb) = cb {
+            cb(limit);
+            self.on_limit_reached.try_lock().unwrap().get_or_insert(cb);
+        }
+    }
+
+    // Makes room for `incoming` rows under the row limit, dropping the oldest rows when the
+    // policy allows it. Returns the range of the incoming rows to append, empty when rejected
+    fn fit_rows(&self, data: &mut CellMatrix, incoming: usize) -> std::ops::Range<usize> {
+        let limits = *self.limits.try_lock().unwrap();
+        let Some(max) = limits.max_rows.filter(|max| data.len() + incoming > *max) else {
+            return 0..incoming;
+        };
+        match limits.policy {
+            OverflowPolicy::Reject => 0..0,
+            OverflowPolicy::DropOldest => {
+                let kept = incoming.min(max);
+                let dropped = (data.len() + kept).saturating_sub(max);
+                self.forget_positions();
+                data.drain(..dropped);
+                self.row_headers.try_lock().unwrap().drain(..dropped);
+                self.row_tags.try_lock().unwrap().drain(..dropped);
+                self.tree.try_lock().unwrap().drain(..dropped);
+                incoming - kept..incoming
+            }
+        }
+    }
+
+    // Column counterpart of fit_rows
+    fn fit_cols(&self, data: &mut CellMatrix, incoming: usize) -> std::ops::Range<usize> {
+        let limits = *self.limits.try_lock().unwrap();
+        let cols = self.col_headers.try_lock().unwrap().len();
+        let Some(max) = limits.max_cols.filter(|max| cols + incoming > *max) else {
+            return 0..incoming;
+        };
+        match limits.policy {
+            OverflowPolicy::Reject => 0..0,
+            OverflowPolicy::DropOldest => {
+                let kept = incoming.min(max);
+                let dropped = (cols + kept).saturating_sub(max);
+                self.forget_positions();
+                for r in data.iter_mut() {
+                    r.drain(..dropped);
+                }
+                self.col_headers.try_lock().unwrap().drain(..dropped);
+                self.col_props.try_lock().unwrap().drain(..dropped);
+                incoming - kept..incoming
+            }
+        }
+    }
+
+    /// Remove a row at the row index
+    pub fn remove_row(&mut self, row: i32) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        data.remove(row as _);
+        self.row_headers.try_lock().unwrap().remove(row as _);
+        self.row_tags.try_lock().unwrap().remove(row as _);
+        self.tree.try_lock().unwrap().remove(row as _);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+    }
+
+    /// Remove the rows in the range of row indices, clamped to the existing rows
+    pub fn remove_rows(&mut self, range: std::ops::Range<i32>) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        let end = (range.end.max(0) as usize).min(data.len());
+        let start = (range.start.max(0) as usize).min(end);
+        if start == end {
+            return;
+        }
+        self.mark_changed();
+        self.forget_positions();
+        data.drain(start..end);
+        self.row_headers.try_lock().unwrap().drain(start..end);
+        self.row_tags.try_lock().unwrap().drain(start..end);
+        self.tree.try_lock().unwrap().drain(start..end);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.table.redraw();
+    }
+
+    /// Remove the selected rows, see `selected_rows()`, and clear the selection.
+    /// Returns the number of rows removed
+    pub fn remove_selected_rows(&mut self) -> usize {
+        if self.has_model() {
+            return 0;
+        }
+        let mut rows: Vec<usize> = self
+            .selected_rows()
+            .into_iter()
+            .map(|r| self.view_to_data_row(r) as usize)
+            .collect();
+        rows.sort_unstable();
+        rows.dedup();
+        self.unset_selection();
+        if rows.is_empty() {
+            return 0;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        Self::remove_indices(&mut data, &rows);
+        Self::remove_indices(&mut self.row_headers.try_lock().unwrap(), &rows);
+        Self::remove_indices(&mut self.row_tags.try_lock().unwrap(), &rows);
+        Self::remove_indices(&mut self.tree.try_lock().unwrap(), &rows);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.table.redraw();
+        rows.len()
+    }
+
+    /// Move a row to another index, along with its header
+    pub fn move_row(&mut self, from: i32, to: i32) {
+        if self.has_model() {
+            return;
+        }
+        let (from, to) = (from as usize, to as usize);
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        Self::move_item(&mut data, from, to);
+        Self::move_item(&mut self.row_headers.try_lock().unwrap(), from, to);
+        Self::move_item(&mut self.row_tags.try_lock().unwrap(), from, to);
+        Self::move_item(&mut self.tree.try_lock().unwrap(), from, to);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.table.redraw();
+    }
+
+    /// Swap two rows, along with their headers
+    pub fn swap_rows(&mut self, a: i32, b: i32) {
+        if self.has_model() {
+            return;
+        }
+        let (a, b) = (a as usize, b as usize);
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        data.swap(a, b);
+        self.row_headers.try_lock().unwrap().swap(a, b);
+        self.row_tags.try_lock().unwrap().swap(a, b);
+        self.tree.try_lock().unwrap().swap(a, b);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.table.redraw();
+    }
+
+    /// Move a column to another index, along with its header, settings and width
+    pub fn move_col(&mut self, from: i32, to: i32) {
+        if self.has_model() {
+            return;
+        }
+        let mut widths = self.col_widths();
+        let (from, to) = (from as usize, to as usize);
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        for r in data.iter_mut() {
+            Self::move_item(r, from, to);
+        }
+        drop(data);
+        Self::move_item(&mut self.col_headers.try_lock().unwrap(), from, to);
+        Self::move_item(&mut self.col_props.try_lock().unwrap(), from, to);
+        Self::move_item(&mut widths, from, to);
+        self.set_col_widths(&widths);
+        let (from, to) = (from as i32, to as i32);
+        self.remap_cols(|c| {
+            Some(if c == from {
+                to
+            } else if from < c && c <= to {
+                c - 1
+            } else if to <= c && c < from {
+                c + 1
+            } else {
+                c
+            })
+        });
+    }
+
+    /// Swap two columns, along with their headers, settings and widths
+    pub fn swap_cols(&mut self, a: i32, b: i32) {
+        if self.has_model() {
+            return;
+        }
+        let mut widths = self.col_widths();
+        let (ua, ub) = (a as usize, b as usize);
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        for r in data.iter_mut() {
+            r.swap(ua, ub);
+        }
+        drop(data);
+        self.col_headers.try_lock().unwrap().swap(ua, ub);
+        self.col_props.try_lock().unwrap().swap(ua, ub);
+        widths.swap(ua, ub);
+        self.set_col_widths(&widths);
+        self.remap_cols(|c| {
+            Some(match c {
+                c if c == a => b,
+                c if c == b => a,
+                c => c,
+            })
+        });
+    }
+
+    // Widths of the data columns, in data order
+    fn col_widths(&self) -> Vec<i32> {
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let cols = self.column_count();
+        (0..cols)
+            .map(|c| self.table.col_width(Self::display_col(rtl, cols, c)))
+            .collect()
+    }
+
+    fn set_col_widths(&mut self, widths: &[i32]) {
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let cols = self.column_count();
+        for (c, w) in widths.iter().enumerate() {
+            self.table
+                .set_col_width(Self::display_col(rtl, cols, c as i32), *w);
+        }
+        self.table.redraw();
+    }
+
+    // Follows the sorted and filtered columns to their new index, None for a removed column
+    fn remap_cols(&mut self, f: impl Fn(i32) -> Option<i32>) {
+        let mut sort = self.sort.try_lock().unwrap();
+        sort.current = sort
+            .current
+            .and_then(|(col, order)| f(col).map(|col| (col, order)));
+        drop(sort);
+        if self.remap_filter_cols(&f) {
+            self.refilter();
+        }
+    }
+
+    fn move_item<T>(v: &mut Vec<T>, from: usize, to: usize) {
+        let item = v.remove(from);
+        v.insert(to, item);
+    }
+
+    // Removes the elements at the indices, which are sorted
+    fn remove_indices<T>(v: &mut Vec<T>, indices: &[usize]) {
+        let mut i = 0;
+        v.retain(|_| {
+            i += 1;
+            indices.binary_search(&(i - 1)).is_err()
+        });
+    }
+
+    /// Remove a column at the column index
+    pub fn remove_col(&mut self, col: i32) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        for v in data.iter_mut() {
+            v.remove(col as _);
+        }
+        drop(data);
+        self.col_headers.try_lock().unwrap().remove(col as _);
+        self.col_props.try_lock().unwrap().remove(col as _);
+        self.table.set_cols(self.table.cols() - 1);
+        Self::clamp_selection(&mut self.table);
+        self.remap_cols(|c| match c.cmp(&col) {
+            std::cmp::Ordering::Less => Some(c),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(c - 1),
+        });
+    }
+
+    /// Set a callback for the SmartTable
+    pub fn set_callback<F: FnMut(&mut Self) + 'static>(&mut self, mut cb: F) {
+        let mut s = self.clone();
+        self.table.set_callback(move |_| {
+            cb(&mut s);
+        });
+    }
+
+    /// Set a callback for on user input
+    /// callback function takes the values row, col, and the new value of the cell
+    pub fn set_on_update_callback<F: FnMut(i32, i32, String) + Send + 'static>(&mut self, cb: F) {
+        *self.on_update_callback.try_lock().unwrap() = Box::new(cb);
     }
 
-    /// Set the cell value, using the row and column to index the data
-    pub fn set_cell_selection_color(&mut self, row: i32, col: i32, color: Color) {
-        self.data.try_lock().unwrap()[row as usize][col as usize].selection_color = Some(color);
+    /// Set a callback called after the editor commits a value into a cell,
+    /// with the row, the column, the previous value and the new one
+    pub fn set_on_cell_change<F: FnMut(&mut SmartTable, i32, i32, &str, &str) + Send + 'static>(
+        &mut self,
+        cb: F,
+    ) {
+        *self.on_cell_change.try_lock().unwrap() = Some(Box::new(cb));
     }
 
-    /// Set the cell value, using the row and column to index the data
-    pub fn set_cell_font_color(&mut self, row: i32, col: i32, color: Color) {
-        self.data.try_lock().unwrap()[row as usize][col as usize].font_color = Some(color);
+    /// Set a callback receiving a spoken-style description of the focused cell
+    /// (its row and column headers and value) whenever the focus moves to another cell.
+    /// FLTK has no accessibility API of its own, so this is the place to forward the text
+    /// to a screen reader bridge or speech engine
+    pub fn set_on_announce_callback<F: FnMut(&str) + Send + 'static>(&mut self, cb: F) {
+        *self.announce_callback.try_lock().unwrap() = Box::new(cb);
     }
 
-    /// Set the cell value, using the row and column to index the data
-    pub fn set_cell_border_color(&mut self, row: i32, col: i32, color: Color) {
-        self.data.try_lock().unwrap()[row as usize][col as usize].border_color = Some(color);
+    /// Show text as a watermark across the visible cells, rotated counter-clockwise by `angle`
+    /// degrees and blended over the cells with the given opacity (0.0 to 1.0)
+    pub fn set_watermark_text(
+        &mut self,
+        text: &str,
+        font: Font,
+        size: i32,
+        color: Color,
+        angle: i32,
+        opacity: f32,
+    ) {
+        use fltk::{prelude::SurfaceDevice, surface::ImageSurface};
+        draw::set_font(font, size);
+        let (tw, th) = (draw::width(text), draw::height() as f64);
+        let (sin, cos) = (angle as f64).to_radians().sin_cos();
+        let w = (tw * cos.abs() + th * sin.abs()).ceil() as i32 + 2;
+        let h = (tw * sin.abs() + th * cos.abs()).ceil() as i32 + 2;
+        let surf = ImageSurface::new(w, h, false);
+        ImageSurface::push_current(&surf);
+        draw::draw_rect_fill(0, 0, w, h, Color::White);
+        draw::set_draw_color(Color::Black);
+        draw::set_font(font, size);
+        // Rotate the baseline origin around the center of the text
+        let (ox, oy) = (tw / 2.0, draw::descent() as f64 - th / 2.0);
+        let (rx, ry) = (ox * cos + oy * sin, -ox * sin + oy * cos);
+        draw::draw_text_angled(
+            angle,
+            text,
+            (w as f64 / 2.0 - rx) as i32,
+            (h as f64 / 2.0 - ry) as i32,
+        );
+        ImageSurface::pop_current();
+        let Some(mask) = surf.image() else {
+            return;
+        };
+        let (r, g, b) = color.to_rgb();
+        let opacity = opacity.clamp(0.0, 1.0);
+        let rgba: Vec<u8> = mask
+            .to_rgb_data()
+            .chunks(3)
+            .flat_map(|p| [r, g, b, ((255 - p[0]) as f32 * opacity) as u8])
+            .collect();
+        *self.watermark.try_lock().unwrap() =
+            fltk::image::RgbImage::new(&rgba, w, h, ColorDepth::Rgba8).ok();
+        self.table.redraw();
     }
 
-    /// Set the cell value, using the row and column to index the data
-    pub fn set_cell_font(&mut self, row: i32, col: i32, font: Font) {
-        self.data.try_lock().unwrap()[row as usize][col as usize].font = Some(font);
+    /// Show an image as a watermark across the visible cells, blended over the cells
+    /// with the given opacity (0.0 to 1.0). It's shrunk to fit the visible area if needed
+    pub fn set_watermark_image<I: ImageExt>(&mut self, image: &I, opacity: f32) {
+        let Ok(rgba) = image
+            .to_rgb_image()
+            .and_then(|i| i.convert(ColorDepth::Rgba8))
+        else {
+            return;
+        };
+        let opacity = opacity.clamp(0.0, 1.0);
+        let data: Vec<u8> = rgba
+            .to_rgb_data()
+            .chunks(4)
+            .flat_map(|p| [p[0], p[1], p[2], (p[3] as f32 * opacity) as u8])
+            .collect();
+        *self.watermark.try_lock().unwrap() =
+            fltk::image::RgbImage::new(&data, rgba.data_w(), rgba.data_h(), ColorDepth::Rgba8).ok();
+        self.table.redraw();
     }
 
-    /// Set the cell value, using the row and column to index the data
-    pub fn set_cell_font_size(&mut self, row: i32, col: i32, sz: i32) {
-        self.data.try_lock().unwrap()[row as usize][col as usize].font_size = Some(sz);
+    /// Remove the watermark
+    pub fn clear_watermark(&mut self) {
+        *self.watermark.try_lock().unwrap() = None;
+        self.table.redraw();
     }
 
-    /// Set the cell value, using the row and column to index the data
-    pub fn set_cell_align(&mut self, row: i32, col: i32, align: Align) {
-        self.data.try_lock().unwrap()[row as usize][col as usize].align = Some(align);
+    /// Set a function drawing over the table once all cells are painted, given the table and
+    /// its visible range. Use `cell_rect()` and `range_rect()` to find where cells are drawn
+    pub fn set_overlay_draw<F: FnMut(&SmartTable, (i32, i32, i32, i32)) + Send + 'static>(
+        &mut self,
+        f: F,
+    ) {
+        *self.overlay.try_lock().unwrap() = Some(Box::new(f));
+        self.table.redraw();
     }
 
-    /// Set the row header value at the row index
-    pub fn set_row_header_value(&mut self, row: i32, val: &str) {
-        self.row_headers.try_lock().unwrap()[row as usize] = val.to_string();
+    /// Remove the overlay drawing function
+    pub fn clear_overlay_draw(&mut self) {
+        *self.overlay.try_lock().unwrap() = None;
+        self.table.redraw();
     }
 
-    /// Set the column header value at the column index
-    pub fn set_col_header_value(&mut self, col: i32, val: &str) {
-        self.col_headers.try_lock().unwrap()[col as usize] = val.to_string();
+    /// Returns the visible and partially visible cells as (row_top, col_left, row_bot, col_right)
+    pub fn visible_range(&self) -> (i32, i32, i32, i32) {
+        let (r1, r2, c1, c2) = self.table.visible_cells();
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let ncols = self.column_count();
+        let cols: Vec<i32> = (c1..=c2)
+            .filter_map(|c| Self::data_col(rtl, ncols, c))
+            .collect();
+        let c1 = cols.iter().copied().min().unwrap_or(0);
+        let c2 = cols.iter().copied().max().unwrap_or(-1);
+        (r1, c1, r2, c2)
     }
 
-    /// Get the row header value at the row index
-    pub fn row_header_value(&mut self, row: i32) -> String {
-        self.row_headers.try_lock().unwrap()[row as usize].clone()
+    /// Set a callback called after the table scrolls or resizes to show other cells,
+    /// e.g. to load the rows coming into view or to keep a side panel in sync.
+    /// Use `visible_range()` to find what's shown
+    pub fn set_on_scroll<F: FnMut(&mut SmartTable) + Send + 'static>(&mut self, cb: F) {
+        *self.on_scroll.try_lock().unwrap() = Some(Box::new(cb));
     }
 
-    /// Get the column header value at the column index
-    pub fn col_header_value(&mut self, col: i32) -> String {
-        self.col_headers.try_lock().unwrap()[col as usize].clone()
+    /// Set a callback called when the view comes within `n` rows of the last row, passed the
+    /// data index of the last shown row, e.g. to fetch the next chunk of an endless table and append it.
+    /// It's called once per row count, so again only after rows were added
+    pub fn set_on_need_more_rows<F: FnMut(&mut SmartTable, i32) + Send + 'static>(
+        &mut self,
+        n: i32,
+        cb: F,
+    ) {
+        *self.on_need_more_rows.try_lock().unwrap() = Some((n.max(0), Box::new(cb)));
+        self.table.redraw();
     }
 
-    /// Insert an empty row at the row index
-    pub fn insert_empty_row(&mut self, row: i32, row_header: &str) {
-        let mut data = self.data.try_lock().unwrap();
-        let cols = self.column_count() as usize;
-        data.insert(row as _, vec![]);
-        data[row as usize].resize(cols as _, Cell::default());
-        self.row_headers
-            .try_lock()
-            .unwrap()
-            .insert(row as _, row_header.to_string());
-        self.table.set_rows(self.table.rows() + 1);
+    /// Returns the screen rectangle (x, y, w, h) of the cell, which may be scrolled out of view
+    pub fn cell_rect(&self, row: i32, col: i32) -> Option<(i32, i32, i32, i32)> {
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let col = Self::display_col(rtl, self.column_count(), col);
+        self.table.find_cell(table::TableContext::Cell, row, col)
     }
 
-    /// Append a row to your table
-    pub fn insert_row(&mut self, row: i32, row_header: &str, vals: &[&str]) {
-        let mut data = self.data.try_lock().unwrap();
-        let cols = self.column_count() as usize;
-        assert!(cols == vals.len());
-        data.insert(row as _, vals.iter().map(|v| Cell::with_label(v)).collect());
-        self.row_headers
-            .try_lock()
-            .unwrap()
-            .push(row_header.to_string());
-        self.table.set_rows(self.table.rows() + 1);
+    /// Scroll so the cell is at the top left of the view, as far as the scrolling allows.
+    /// Like `cell_rect()`, the row is the shown one and the column the data one
+    pub fn scroll_to(&mut self, row: i32, col: i32) {
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let col = Self::display_col(rtl, self.column_count(), col);
+        self.table
+            .set_row_position(row.clamp(0, (self.table.rows() - 1).max(0)));
+        self.table.set_col_position(col.max(0));
+        self.table.redraw();
     }
 
-    /// Append an empty row to your table
-    pub fn append_empty_row(&mut self, row_header: &str) {
-        let mut data = self.data.try_lock().unwrap();
-        let cols = self.column_count() as usize;
-        data.push(vec![]);
-        data.last_mut().unwrap().resize(cols as _, Cell::default());
-        self.row_headers
-            .try_lock()
-            .unwrap()
-            .push(row_header.to_string());
-        self.table.set_rows(self.table.rows() + 1);
+    /// Scroll the least needed to show the whole cell, e.g. after selecting it or appending it
+    /// to a log-style table. Returns whether the view moved
+    pub fn ensure_visible(&mut self, row: i32, col: i32) -> bool {
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let col = Self::display_col(rtl, self.column_count(), col);
+        let Some((x, y, w, h)) = self.table.find_cell(table::TableContext::Cell, row, col) else {
+            return false;
+        };
+        let (x0, y0, x1, y1) = Self::cells_area(&self.table);
+        // The first of the items before `last` fitting with it in `span`
+        let first_fitting = |last: i32, span: i32, size: &dyn Fn(i32) -> i32| {
+            let mut first = last;
+            let mut used = size(last);
+            while first > 0 && used + size(first - 1) <= span {
+                first -= 1;
+                used += size(first);
+            }
+            first
+        };
+        let mut moved = false;
+        if y < y0 {
+            self.table.set_row_position(row);
+            moved = true;
+        } else if y + h > y1 {
+            let t = self.table.clone();
+            let top = first_fitting(row, y1 - y0, &|r| t.row_height(r));
+            self.table.set_row_position(top);
+            moved = true;
+        }
+        if x < x0 {
+            self.table.set_col_position(col);
+            moved = true;
+        } else if x + w > x1 {
+            let t = self.table.clone();
+            let left = first_fitting(col, x1 - x0, &|c| t.col_width(c));
+            self.table.set_col_position(left);
+            moved = true;
+        }
+        if moved {
+            self.table.redraw();
+        }
+        moved
     }
 
-    /// Append a row to your table
-    pub fn append_row(&mut self, row_header: &str, vals: &[&str]) {
-        let mut data = self.data.try_lock().unwrap();
-        let cols = self.column_count() as usize;
-        assert!(cols == vals.len());
-        data.push(vals.iter().map(|v| Cell::with_label(v)).collect());
-        self.row_headers
-            .try_lock()
-            .unwrap()
-            .push(row_header.to_string());
-        self.table.set_rows(self.table.rows() + 1);
+    /// Returns the screen rectangle (x, y, w, h) bounding the range of cells
+    pub fn range_rect(&self, r1: i32, c1: i32, r2: i32, c2: i32) -> Option<(i32, i32, i32, i32)> {
+        let (ax, ay, aw, ah) = self.cell_rect(r1, c1)?;
+        let (bx, by, bw, bh) = self.cell_rect(r2, c2)?;
+        let (x, y) = (ax.min(bx), ay.min(by));
+        Some((x, y, (ax + aw).max(bx + bw) - x, (ay + ah).max(by + bh) - y))
     }
 
-    /// Insert an empty column at the column index
-    pub fn insert_empty_col(&mut self, col: i32, col_header: &str) {
-        let mut data = self.data.try_lock().unwrap();
-        for v in data.iter_mut() {
-            v.insert(col as _, Cell::default());
-        }
-        self.col_headers
-            .try_lock()
-            .unwrap()
-            .insert(col as _, col_header.to_string());
-        self.table.set_cols(self.table.cols() + 1);
+    /// Returns the focused cell, which is the anchor of the current selection
+    pub fn focused_cell(&self) -> Option<(i32, i32)> {
+        Self::focus_of(&self.table)
     }
 
-    /// Append a column to your table
-    pub fn insert_col(&mut self, col: i32, col_header: &str, vals: &[&str]) {
-        let mut data = self.data.try_lock().unwrap();
-        assert!(vals.len() == self.table.rows() as usize);
-        let mut count = 0;
-        for v in data.iter_mut() {
-            v.insert(col as _, Cell::with_label(vals[count]));
-            count += 1;
+    /// Copy the selected cells to the clipboard as tab-separated text, one line per row,
+    /// which spreadsheets paste into separate cells. Also bound to Ctrl+C.
+    /// Returns whether anything was selected
+    pub fn copy_selection(&self) -> bool {
+        let Some(sel) = self
+            .table
+            .try_get_selection()
+            .filter(|s| s.0 >= 0 && s.1 >= 0)
+        else {
+            return false;
+        };
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let txt = Self::range_text(
+            &self.data.try_lock().unwrap(),
+            self.model.try_lock().unwrap().as_deref(),
+            &self.filter.try_lock().unwrap(),
+            sel,
+            rtl,
+        );
+        app::copy(&txt);
+        true
+    }
+
+    // Shrinks the selection to the table's bounds, clearing it when it's entirely outside
+    fn clamp_selection(t: &mut table::TableRow) {
+        let (rows, cols) = (t.rows(), t.cols());
+        match t.try_get_selection().filter(|s| s.0 >= 0 && s.1 >= 0) {
+            Some((r1, c1, r2, c2)) if r1 < rows && c1 < cols => {
+                t.set_selection(r1, c1, r2.min(rows - 1), c2.min(cols - 1))
+            }
+            Some(_) => t.unset_selection(),
+            None => (),
         }
-        self.col_headers
-            .try_lock()
-            .unwrap()
-            .push(col_header.to_string());
-        self.table.set_cols(self.table.cols() + 1);
     }
 
-    /// Append an empty column to your table
-    pub fn append_empty_col(&mut self, col_header: &str) {
-        let mut data = self.data.try_lock().unwrap();
-        for v in data.iter_mut() {
-            v.push(Cell::default());
+    /// Select the cell, replacing the selection
+    pub fn select_cell(&mut self, row: i32, col: i32) {
+        self.select_range(row, col, row, col);
+    }
+
+    /// Select the range of cells between the two corners, replacing the selection.
+    /// In the row selection modes the rows of the range are selected
+    pub fn select_range(&mut self, r1: i32, c1: i32, r2: i32, c2: i32) {
+        let (c1, c2) = (self.inner_col(c1), self.inner_col(c2));
+        let (r1, r2) = (r1.min(r2), r1.max(r2));
+        self.table.set_selection(r1, c1.min(c2), r2, c1.max(c2));
+        if self.opts.try_lock().unwrap().selection_mode != SelectionMode::Cell {
+            self.table
+                .select_all_rows(table::TableRowSelectFlag::Deselect);
+            for r in r1..=r2 {
+                self.table
+                    .select_row(r, table::TableRowSelectFlag::Select)
+                    .ok();
+            }
         }
-        self.col_headers
-            .try_lock()
-            .unwrap()
-            .push(col_header.to_string());
-        self.table.set_cols(self.table.cols() + 1);
+        self.table.redraw();
     }
 
-    /// Append a column to your table
-    pub fn append_col(&mut self, col_header: &str, vals: &[&str]) {
-        let mut data = self.data.try_lock().unwrap();
-        assert!(vals.len() == self.table.rows() as usize);
-        let mut count = 0;
-        for v in data.iter_mut() {
-            v.push(Cell::with_label(vals[count]));
-            count += 1;
+    /// Returns the selected cells as (row, col), row by row
+    pub fn selected_cells(&self) -> Vec<(i32, i32)> {
+        let (mode, rtl) = {
+            let opts = self.opts.try_lock().unwrap();
+            (opts.selection_mode, opts.rtl)
+        };
+        let ncols = self.column_count();
+        let cols: Vec<i32> = match mode {
+            SelectionMode::Cell => {
+                let Some((_, c1, _, c2)) = self
+                    .table
+                    .try_get_selection()
+                    .filter(|s| s.0 >= 0 && s.1 >= 0)
+                else {
+                    return vec![];
+                };
+                let mut cols: Vec<i32> = (c1..=c2)
+                    .filter_map(|c| Self::data_col(rtl, ncols, c))
+                    .collect();
+                cols.sort_unstable();
+                cols
+            }
+            SelectionMode::Row | SelectionMode::MultiRow => (0..ncols).collect(),
+        };
+        self.selected_rows()
+            .into_iter()
+            .flat_map(|r| cols.iter().map(move |c| (r, *c)))
+            .collect()
+    }
+
+    /// Clear the contents of the selected cells as one undoable step, also bound to Delete and
+    /// Backspace in editable tables. Locked cells and read-only columns are left as is.
+    /// Returns whether anything was cleared
+    pub fn clear_selection_contents(&mut self) -> bool {
+        let mut edits = vec![];
+        for (row, col) in self.selected_cells() {
+            let row = self.view_to_data_row(row);
+            let locked = self
+                .data
+                .try_lock()
+                .unwrap()
+                .get(row as usize)
+                .and_then(|r| r.get(col as usize))
+                .is_some_and(|c| c.locked)
+                || self.cell_read_only(row, col);
+            let old = self.cell_value(row, col);
+            if locked || old.is_empty() {
+                continue;
+            }
+            self.store_cell(row, col, "");
+            edits.push((row, col, old, String::new()));
         }
-        self.col_headers
-            .try_lock()
-            .unwrap()
-            .push(col_header.to_string());
-        self.table.set_cols(self.table.cols() + 1);
+        if edits.is_empty() {
+            return false;
+        }
+        let depth = self.opts.try_lock().unwrap().undo_depth;
+        self.undo_stack.try_lock().unwrap().push_step(edits, depth);
+        self.table.redraw();
+        true
     }
 
-    /// Remove a row at the row index
-    pub fn remove_row(&mut self, row: i32) {
-        let mut data = self.data.try_lock().unwrap();
-        data.remove(row as _);
-        self.row_headers.try_lock().unwrap().remove(row as _);
-        self.table.set_rows(self.table.rows() - 1);
+    /// Clear the selection
+    pub fn unset_selection(&mut self) {
+        self.table.unset_selection();
+        self.table
+            .select_all_rows(table::TableRowSelectFlag::Deselect);
+        self.table.redraw();
     }
 
-    /// Remove a column at the column index
-    pub fn remove_col(&mut self, col: i32) {
-        let mut data = self.data.try_lock().unwrap();
-        for v in data.iter_mut() {
-            v.remove(col as _);
+    /// Returns the selected rows: in the row selection modes the highlighted rows,
+    /// otherwise the rows spanned by the selected cells
+    pub fn selected_rows(&self) -> Vec<i32> {
+        match self.opts.try_lock().unwrap().selection_mode {
+            SelectionMode::Cell => self
+                .table
+                .try_get_selection()
+                .filter(|s| s.0 >= 0 && s.1 >= 0)
+                .map_or(vec![], |(r1, _, r2, _)| (r1.min(r2)..=r1.max(r2)).collect()),
+            SelectionMode::Row | SelectionMode::MultiRow => {
+                let mut t = self.table.clone();
+                (0..t.rows()).filter(|r| t.row_selected(*r)).collect()
+            }
         }
-        self.col_headers.try_lock().unwrap().remove(col as _);
-        self.table.set_cols(self.table.cols() - 1);
     }
 
-    /// Set a callback for the SmartTable
-    pub fn set_callback<F: FnMut(&mut Self) + 'static>(&mut self, mut cb: F) {
-        let mut s = self.clone();
-        self.table.set_callback(move |_| {
-            cb(&mut s);
-        });
+    /// Set a callback called when the user changes the selection
+    pub fn set_on_selection_changed<F: FnMut(&mut SmartTable) + Send + 'static>(&mut self, cb: F) {
+        *self.on_selection_changed.try_lock().unwrap() = Some(Box::new(cb));
     }
 
-    /// Set a callback for on user input
-    /// callback function takes the values row, col, and the new value of the cell
-    pub fn set_on_update_callback<F: FnMut(i32, i32, String) + Send + 'static>(&mut self, cb: F) {
-        *self.on_update_callback.try_lock().unwrap() = Box::new(cb);
+    /// Returns a description of the cell made of its row and column headers and its value,
+    /// suitable for assistive technology
+    pub fn cell_description(&self, row: i32, col: i32) -> String {
+        Self::describe(
+            &self.data.try_lock().unwrap(),
+            &self.row_headers.try_lock().unwrap(),
+            &self.col_headers.try_lock().unwrap(),
+            row,
+            col,
+        )
     }
 
-    /// Clears all cells in the table
+    /// Clears all cells in the table, keeping the rows and columns, see `clear_all()`
     pub fn clear(&mut self) {
         let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
         for v in data.iter_mut() {
             for c in v.iter_mut() {
                 *c = Cell::default();
@@ -596,6 +4053,36 @@ impl SmartTable {
         }
     }
 
+    /// Remove all the rows, and the columns unless `keep_cols` is set, in which case the
+    /// columns keep their headers and settings and the table can be refilled with `append_row()`.
+    /// Otherwise `append_empty_col()` or `extend_rows()` rebuild the columns
+    pub fn clear_all(&mut self, keep_cols: bool) {
+        if self.has_model() {
+            return;
+        }
+        let mut data = self.data.try_lock().unwrap();
+        self.mark_changed();
+        self.forget_positions();
+        data.clear();
+        self.row_headers.try_lock().unwrap().clear();
+        self.row_tags.try_lock().unwrap().clear();
+        self.tree.try_lock().unwrap().clear();
+        if !keep_cols {
+            self.col_headers.try_lock().unwrap().clear();
+            self.col_props.try_lock().unwrap().clear();
+        }
+        self.table.unset_selection();
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.sync_cols();
+    }
+
     /// Returns the row count
     pub fn row_count(&self) -> i32 {
         self.table.rows()
@@ -603,12 +4090,261 @@ impl SmartTable {
 
     /// Returns the column count
     pub fn column_count(&self) -> i32 {
-        self.table.cols()
+        self.table.cols() - self.opts.try_lock().unwrap().rtl as i32
+    }
+
+    // The inner table's column displaying the data column
+    fn inner_col(&self, col: i32) -> i32 {
+        Self::display_col(self.opts.try_lock().unwrap().rtl, self.column_count(), col)
+    }
+
+    /// Set the alignment of a column's cells, cells with their own alignment take precedence
+    pub fn set_col_align(&mut self, col: i32, align: Align) {
+        self.col_props.try_lock().unwrap()[col as usize].align = Some(align);
+    }
+
+    /// Configure the leading columns, one `ColOpts` per column, also applied again by
+    /// `set_opts()`. For example:
+    /// `table.set_col_opts(vec![ColOpts::new().width(ColWidth::Fixed(60)).align(Align::Right)]);`
+    pub fn set_col_opts(&mut self, col_opts: Vec<ColOpts>) {
+        *self.col_opts.try_lock().unwrap() = col_opts;
+        self.apply_col_opts();
+        let opts = *self.opts.try_lock().unwrap();
+        Self::apply_width_modes(&mut self.table, &self.col_props.try_lock().unwrap(), &opts);
+        self.table.redraw();
+    }
+
+    fn apply_col_opts(&self) {
+        let col_opts = self.col_opts.try_lock().unwrap();
+        let mut col_props = self.col_props.try_lock().unwrap();
+        for (props, co) in col_props.iter_mut().zip(col_opts.iter()) {
+            props.width = co.width;
+            props.align = co.align;
+            props.read_only = !co.editable;
+            props.wrap = co.wrap;
+        }
+    }
+
+    /// Set whether a column's text is drawn right to left, overriding [`TableOpts::rtl`]
+    pub fn set_col_rtl(&mut self, col: i32, rtl: bool) {
+        self.col_props.try_lock().unwrap()[col as usize].rtl = Some(rtl);
+    }
+
+    // Whether the cell's column isn't editable or the read-only predicate excludes the cell
+    pub(crate) fn cell_read_only(&self, row: i32, col: i32) -> bool {
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .get(col as usize)
+            .is_some_and(|p| p.read_only)
+            || self
+                .readonly_predicate
+                .try_lock()
+                .unwrap()
+                .as_ref()
+                .is_some_and(|f| f(row, col))
+    }
+
+    /// Set whether the column's cells can be edited in an editable table, e.g. to protect
+    /// IDs or computed values
+    pub fn set_col_editable(&mut self, col: i32, editable: bool) {
+        self.col_props.try_lock().unwrap()[col as usize].read_only = !editable;
+    }
+
+    /// Set a predicate given the row and column of a cell, returning true for the cells which
+    /// can't be edited. It's checked along with `set_col_editable()`
+    pub fn set_readonly_predicate<F: Fn(i32, i32) -> bool + Send + 'static>(&mut self, f: F) {
+        *self.readonly_predicate.try_lock().unwrap() = Some(Box::new(f));
+    }
+
+    /// Remove the read-only predicate
+    pub fn clear_readonly_predicate(&mut self) {
+        *self.readonly_predicate.try_lock().unwrap() = None;
+    }
+
+    /// Set how the column's width is computed. Percentages and stretching columns are
+    /// recalculated whenever the table is resized
+    pub fn set_col_width_mode(&mut self, col: i32, mode: ColWidth) {
+        let mut col_props = self.col_props.try_lock().unwrap();
+        col_props[col as usize].width = Some(mode);
+        let opts = *self.opts.try_lock().unwrap();
+        Self::apply_width_modes(&mut self.table, &col_props, &opts);
+    }
+
+    // Sets the widths of the columns having a width mode from the width available to the columns
+    // and from the column layout
+    fn apply_width_modes(t: &mut table::TableRow, col_props: &[ColProps], opts: &TableOpts) {
+        let rtl = opts.rtl;
+        if opts.col_layout == ColLayout::Fixed && col_props.iter().all(|p| p.width.is_none()) {
+            return;
+        }
+        let (x0, _, x1, _) = Self::cells_area(t);
+        let avail = x1 - x0;
+        let ncols = col_props.len() as i32;
+        // The last visible column without a width mode stretches in the StretchLast layout
+        let last = (0..ncols)
+            .rev()
+            .find(|d| col_props[*d as usize].hidden.is_none())
+            .filter(|d| col_props[*d as usize].width.is_none())
+            .filter(|_| opts.col_layout == ColLayout::StretchLast)
+            .map(|d| Self::display_col(rtl, ncols, d));
+        let mut used = 0;
+        let mut stretch = vec![];
+        let mut free = vec![];
+        for c in 0..t.cols() {
+            if Some(c) == last {
+                stretch.push(c);
+                continue;
+            }
+            let props = Self::data_col(rtl, ncols, c).map(|d| &col_props[d as usize]);
+            if props.is_some_and(|p| p.hidden.is_some()) {
+                // Hidden columns stay collapsed
+                if t.col_width(c) != 0 {
+                    t.set_col_width(c, 0);
+                }
+                continue;
+            }
+            let w = match props.and_then(|p| p.width) {
+                Some(ColWidth::Fixed(px)) => px,
+                Some(ColWidth::Percent(p)) => (avail as f32 * p / 100.0) as i32,
+                Some(ColWidth::Stretch) => {
+                    stretch.push(c);
+                    continue;
+                }
+                None => {
+                    if props.is_some() {
+                        free.push(c);
+                    }
+                    t.col_width(c)
+                }
+            };
+            if w != t.col_width(c) {
+                t.set_col_width(c, w);
+            }
+            used += w;
+        }
+        let n = stretch.len() as i32;
+        if n > 0 {
+            let left = (avail - used).max(0);
+            for (i, c) in stretch.iter().enumerate() {
+                // The last one takes the rounding remainder
+                let w = if i as i32 == n - 1 {
+                    left - left / n * (n - 1)
+                } else {
+                    left / n
+                };
+                t.set_col_width(*c, w.max(1));
+            }
+        } else if opts.col_layout == ColLayout::Proportional {
+            // Scales the columns without a width mode to fill what the others leave
+            let total: i32 = free.iter().map(|c| t.col_width(*c)).sum();
+            let fixed = used - total;
+            let target = avail - fixed;
+            if total > 0 && target > 0 && target != total {
+                let mut left = target;
+                for (i, c) in free.iter().enumerate() {
+                    let w = if i == free.len() - 1 {
+                        left
+                    } else {
+                        (t.col_width(*c) as i64 * target as i64 / total as i64) as i32
+                    };
+                    left -= w;
+                    t.set_col_width(*c, w.max(1));
+                }
+            }
+        }
+        t.redraw();
+    }
+
+    // Shows or hides the headers, in right-to-left mode the row headers are the trailing column
+    fn apply_headers(t: &mut table::TableRow, opts: &TableOpts) {
+        t.set_row_header(opts.show_row_headers && !opts.rtl);
+        t.set_col_header(opts.show_col_headers);
+        if opts.rtl {
+            let w = if opts.show_row_headers {
+                t.row_header_width()
+            } else {
+                0
+            };
+            t.set_col_width(t.cols() - 1, w);
+        }
+    }
+
+    /// Show or hide the row headers
+    pub fn set_show_row_headers(&mut self, show: bool) {
+        self.opts.try_lock().unwrap().show_row_headers = show;
+        self.refresh_headers();
+    }
+
+    /// Show or hide the column headers
+    pub fn set_show_col_headers(&mut self, show: bool) {
+        self.opts.try_lock().unwrap().show_col_headers = show;
+        self.refresh_headers();
+    }
+
+    // Applies the changed header options and refits the columns to the new cells area
+    fn refresh_headers(&mut self) {
+        let opts = *self.opts.try_lock().unwrap();
+        Self::apply_headers(&mut self.table, &opts);
+        Self::apply_width_modes(&mut self.table, &self.col_props.try_lock().unwrap(), &opts);
+        self.table.redraw();
+    }
+
+    /// Set whether the last row is kept in view as rows are appended, see `TableOpts::follow_tail`
+    pub fn set_follow_tail(&mut self, follow: bool) {
+        self.opts.try_lock().unwrap().follow_tail = follow;
+        if follow {
+            let rows = self.table.rows();
+            if rows > 0 {
+                let col = self.visible_range().1;
+                self.ensure_visible(rows - 1, col);
+            }
+        }
+    }
+
+    /// Set how the columns fill the width of the table
+    pub fn set_col_layout(&mut self, layout: ColLayout) {
+        self.opts.try_lock().unwrap().col_layout = layout;
+        let opts = *self.opts.try_lock().unwrap();
+        Self::apply_width_modes(&mut self.table, &self.col_props.try_lock().unwrap(), &opts);
+    }
+
+    /// Hide the column, keeping its data. It's collapsed to a width of 0 until shown again
+    pub fn hide_col(&mut self, col: i32) {
+        let inner = self.inner_col(col);
+        let mut col_props = self.col_props.try_lock().unwrap();
+        let props = &mut col_props[col as usize];
+        if props.hidden.is_none() {
+            props.hidden = Some(self.table.col_width(inner));
+            self.table.set_col_width(inner, 0);
+            self.table.redraw();
+        }
+    }
+
+    /// Show a column hidden with `hide_col()` at its previous width
+    pub fn show_col(&mut self, col: i32) {
+        let inner = self.inner_col(col);
+        let mut col_props = self.col_props.try_lock().unwrap();
+        if let Some(w) = col_props[col as usize].hidden.take() {
+            self.table.set_col_width(inner, w);
+            let opts = *self.opts.try_lock().unwrap();
+            Self::apply_width_modes(&mut self.table, &col_props, &opts);
+            self.table.redraw();
+        }
+    }
+
+    /// Returns whether the column is shown
+    pub fn is_col_visible(&self, col: i32) -> bool {
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .get(col as usize)
+            .is_some_and(|p| p.hidden.is_none())
     }
 
     /// Get the column's width
     pub fn col_width(&self, col: i32) -> i32 {
-        self.table.col_width(col)
+        self.table.col_width(self.inner_col(col))
     }
 
     /// Get the row's height
@@ -618,6 +4354,7 @@ impl SmartTable {
 
     /// Set column's width
     pub fn set_col_width(&mut self, col: i32, width: i32) {
+        let col = self.inner_col(col);
         self.table.set_col_width(col, width);
     }
 
@@ -626,6 +4363,29 @@ impl SmartTable {
         self.table.set_row_height(row, height);
     }
 
+    /// Get the size fitting the headers, all columns and at most `max_rows` rows,
+    /// with room for a vertical scrollbar when there are more rows.
+    /// Useful for sizing small tables in `Flex` or `Grid` layouts
+    pub fn preferred_size(&self, max_rows: i32) -> (i32, i32) {
+        let t = &self.table;
+        let frame = t.frame();
+        let mut w = frame.dw() + (0..t.cols()).map(|c| t.col_width(c)).sum::<i32>();
+        let mut h = frame.dh()
+            + (0..t.rows().min(max_rows))
+                .map(|r| t.row_height(r))
+                .sum::<i32>();
+        if t.row_header() {
+            w += t.row_header_width();
+        }
+        if t.col_header() {
+            h += t.col_header_height();
+        }
+        if t.rows() > max_rows {
+            w += t.scrollbar_size().max(app::scrollbar_size());
+        }
+        (w, h)
+    }
+
     /// Get the column header height
     pub fn col_header_height(&self) -> i32 {
         self.table.col_header_height()
@@ -647,6 +4407,77 @@ impl SmartTable {
     }
 }
 
+/// A snapshot of a table's contents taken by `SmartTable::checkpoint()`
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    data: CellMatrix,
+    row_headers: Vec<String>,
+    col_headers: Vec<String>,
+    row_tags: Vec<Option<RowTag>>,
+    tree: tree::TreeNodes,
+    col_props: Vec<ColProps>,
+}
+
+/// Builds a row cell by cell, appending it to the table on `commit()`
+#[derive(Debug)]
+pub struct RowBuilder<'a> {
+    table: &'a mut SmartTable,
+    header: Option<String>,
+    cells: Vec<String>,
+}
+
+impl RowBuilder<'_> {
+    /// Set the row header, it defaults to the row number
+    pub fn header<S: Into<String>>(mut self, header: S) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    /// Add the next cell
+    pub fn cell<T: ToString>(mut self, val: T) -> Self {
+        self.cells.push(val.to_string());
+        self
+    }
+
+    /// Append the row, returning its index, or `None` if the row limit rejected it
+    /// or a model is set. Panics if the number of cells doesn't match the number of columns
+    pub fn commit(self) -> Option<i32> {
+        if self.table.has_model() {
+            return None;
+        }
+        let cols = self.table.column_count() as usize;
+        assert!(
+            cols == self.cells.len(),
+            "row has {} cells, the table has {} columns",
+            self.cells.len(),
+            cols
+        );
+        self.table.notify_limit(TableLimit::Rows, 1);
+        let mut data = self.table.data.try_lock().unwrap();
+        self.table.mark_changed();
+        if self.table.fit_rows(&mut data, 1).is_empty() {
+            return None;
+        }
+        let row = data.len();
+        data.push(self.cells.into_iter().map(Cell::from_string).collect());
+        self.table.row_headers.try_lock().unwrap().push(
+            self.header
+                .unwrap_or_else(|| self.table.row_label(row as _)),
+        );
+        self.table.row_tags.try_lock().unwrap().push(None);
+        self.table.tree.try_lock().unwrap().push(None);
+        SmartTable::sync_rows(
+            &mut self.table.table,
+            &self.table.filter,
+            &self.table.model,
+            &data,
+            &self.table.footer,
+        );
+        self.table.table.redraw();
+        Some(row as i32)
+    }
+}
+
 impl std::fmt::Debug for SmartTable {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         fmt.debug_struct("SmartTable")
@@ -659,3 +4490,32 @@ impl std::fmt::Debug for SmartTable {
 }
 
 fltk::widget_extends!(SmartTable, table::TableRow, table);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_col_headers() {
+        let header = SmartTable::default_col_header;
+        assert_eq!(header(0), "A");
+        assert_eq!(header(25), "Z");
+        assert_eq!(header(26), "AA");
+        assert_eq!(header(701), "ZZ");
+        assert_eq!(header(702), "AAA");
+        assert_eq!(header(703), "AAB");
+    }
+
+    #[test]
+    fn move_and_remove_items() {
+        let mut v = vec![0, 1, 2, 3, 4];
+        SmartTable::move_item(&mut v, 1, 3);
+        assert_eq!(v, [0, 2, 3, 1, 4]);
+        SmartTable::move_item(&mut v, 4, 0);
+        assert_eq!(v, [4, 0, 2, 3, 1]);
+        SmartTable::remove_indices(&mut v, &[0, 2, 4]);
+        assert_eq!(v, [0, 3]);
+        SmartTable::remove_indices(&mut v, &[]);
+        assert_eq!(v, [0, 3]);
+    }
+}
@@ -0,0 +1,96 @@
+// Hyperlink-style cells, underlined and calling a callback when clicked
+
+use super::SmartTable;
+use fltk::{
+    app, draw,
+    enums::*,
+    prelude::{TableExt, WidgetExt},
+    table,
+};
+
+pub(crate) type LinkCallback = Box<dyn FnMut(i32, i32, &str) + Send>;
+
+impl SmartTable {
+    /// Make the cell a link, drawn underlined in `TableOpts::link_color`.
+    /// Clicking it calls the `set_on_link_click()` callback instead of editing it
+    pub fn set_cell_link(&mut self, row: i32, col: i32, link: bool) {
+        if self.has_model() {
+            return;
+        }
+        self.data.try_lock().unwrap()[row as usize][col as usize].link = link;
+        self.table.redraw();
+    }
+
+    /// Returns whether the cell is a link
+    pub fn is_cell_link(&self, row: i32, col: i32) -> bool {
+        !self.has_model() && self.data.try_lock().unwrap()[row as usize][col as usize].link
+    }
+
+    /// Set a callback called with the row, the column and the text of a clicked link
+    pub fn set_on_link_click<F: FnMut(i32, i32, &str) + Send + 'static>(&mut self, cb: F) {
+        *self.on_link_click.try_lock().unwrap() = Some(Box::new(cb));
+    }
+
+    // The data row, column and text of the link under the mouse
+    fn link_at(&self, t: &table::TableRow, rtl: bool) -> Option<(i32, i32, String)> {
+        let Some((table::TableContext::Cell, view_row, view_col, _)) = t.cursor2rowcol() else {
+            return None;
+        };
+        let col = Self::data_col(rtl, self.column_count(), view_col)?;
+        let row = self.view_to_data_row(view_row);
+        let data = self.data.try_lock().ok()?;
+        let cell = data.get(row as usize)?.get(col as usize)?;
+        cell.link.then(|| (row, col, cell.label.clone()))
+    }
+
+    // Shows the hand cursor over links and calls the callback when one is clicked.
+    // `hovering` tracks whether the cursor was changed
+    pub(crate) fn handle_link(
+        &mut self,
+        t: &table::TableRow,
+        ev: Event,
+        rtl: bool,
+        hovering: &mut bool,
+    ) -> bool {
+        match ev {
+            Event::Move | Event::Enter | Event::Leave => {
+                let over = ev != Event::Leave && self.link_at(t, rtl).is_some();
+                if over != *hovering {
+                    *hovering = over;
+                    if let Some(mut win) = t.window() {
+                        win.set_cursor(if over { Cursor::Hand } else { Cursor::Default });
+                    }
+                }
+                false
+            }
+            Event::Released
+                if app::event_is_click() && app::event_mouse_button() == app::MouseButton::Left =>
+            {
+                let Some((row, col, text)) = self.link_at(t, rtl) else {
+                    return false;
+                };
+                let cb = self.on_link_click.clone();
+                if let Some(cb) = &mut *cb.try_lock().unwrap() {
+                    cb(row, col, &text);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Underlines the text drawn by draw_text2 within x, y, w, h, in the current font and color
+    pub(crate) fn draw_underline(txt: &str, x: i32, y: i32, w: i32, h: i32, align: Align) {
+        let (tw, th) = draw::measure(txt, false);
+        let tw = tw.min(w);
+        let lx = if align.contains(Align::Left) {
+            x
+        } else if align.contains(Align::Right) {
+            x + w - tw
+        } else {
+            x + (w - tw) / 2
+        };
+        let baseline = y + (h + th) / 2 - draw::descent();
+        draw::draw_xyline(lx, baseline + 1, lx + tw);
+    }
+}
@@ -0,0 +1,97 @@
+// Input masks of the inline editor, compiled in with the `editing` feature
+
+use super::{CellEditor, SmartTable};
+use fltk::{app, enums::*, input, prelude::InputExt};
+
+// Whether the character can fill the slot of the mask: 9 takes a digit, A a letter,
+// * a letter or digit, and any other character only itself
+fn slot_accepts(slot: char, c: char) -> bool {
+    match slot {
+        '9' => c.is_ascii_digit(),
+        'A' => c.is_alphabetic(),
+        '*' => c.is_alphanumeric(),
+        lit => c == lit,
+    }
+}
+
+fn is_literal(slot: char) -> bool {
+    !matches!(slot, '9' | 'A' | '*')
+}
+
+// Whether the text is the start of a value matching the mask
+pub(crate) fn mask_fits(mask: &str, text: &str) -> bool {
+    text.chars().count() <= mask.chars().count()
+        && mask
+            .chars()
+            .zip(text.chars())
+            .all(|(m, c)| slot_accepts(m, c))
+}
+
+// Whether the text fills the whole mask, or is empty
+pub(crate) fn mask_complete(mask: &str, text: &str) -> bool {
+    text.is_empty() || (mask_fits(mask, text) && text.chars().count() == mask.chars().count())
+}
+
+impl SmartTable {
+    // Rejects the keystrokes and pastes which would break the mask of column `col`,
+    // typing over a literal of the mask inserts it. Returns whether the event was used
+    pub(crate) fn handle_mask(&mut self, inp: &mut input::Input, ev: Event, col: i32) -> bool {
+        if !matches!(ev, Event::KeyDown | Event::Paste) {
+            return false;
+        }
+        let CellEditor::Mask(mask) = self.col_editor(col) else {
+            return false;
+        };
+        let typed = app::event_text();
+        if typed.is_empty() || typed.chars().any(char::is_control) {
+            // Navigation and deletion keys
+            return false;
+        }
+        let value = inp.value();
+        let (start, end) = {
+            let (p, m) = (inp.position() as usize, inp.mark() as usize);
+            (p.min(m).min(value.len()), p.max(m).min(value.len()))
+        };
+        let new = format!("{}{typed}{}", &value[..start], &value[end..]);
+        if mask_fits(&mask, &new) {
+            return false;
+        }
+        // Fills the literals the typed text skipped over, e.g. the dashes of a date
+        let literals: String = mask
+            .chars()
+            .skip(value[..start].chars().count())
+            .take_while(|m| is_literal(*m))
+            .collect();
+        let filled = format!("{literals}{typed}");
+        let new = format!("{}{filled}{}", &value[..start], &value[end..]);
+        if !literals.is_empty() && mask_fits(&mask, &new) {
+            inp.replace(start as i32, end as i32, &filled).ok();
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits() {
+        assert!(mask_fits("99/99", ""));
+        assert!(mask_fits("99/99", "12/"));
+        assert!(mask_fits("99/99", "12/34"));
+        assert!(!mask_fits("99/99", "12-"));
+        assert!(!mask_fits("99/99", "1a"));
+        assert!(!mask_fits("99/99", "12/345"));
+        assert!(mask_fits("AA-**", "xy-a1"));
+        assert!(!mask_fits("**", "a-"));
+    }
+
+    #[test]
+    fn complete() {
+        assert!(mask_complete("AA-999", ""));
+        assert!(!mask_complete("AA-999", "ab-12"));
+        assert!(mask_complete("AA-999", "ab-123"));
+        assert!(!mask_complete("AA-999", "ab-12x"));
+    }
+}
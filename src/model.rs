@@ -0,0 +1,125 @@
+// Custom data backends a SmartTable can display instead of its own matrix
+
+use super::{ColProps, SmartTable};
+use fltk::prelude::*;
+
+/// A data source for a SmartTable, letting the data live in the application's own structures.
+/// Only the visible cells are requested while drawing
+pub trait TableModel: Send {
+    /// Number of rows
+    fn row_count(&self) -> i32;
+    /// Number of columns
+    fn col_count(&self) -> i32;
+    /// Text of the cell
+    fn cell(&self, row: i32, col: i32) -> String;
+    /// Store a value edited in the table, read-only models can ignore it
+    fn set_cell(&mut self, _row: i32, _col: i32, _val: &str) {}
+    /// Row header, the row number by default
+    fn row_header(&self, row: i32) -> String {
+        (row + 1).to_string()
+    }
+    /// Column header, A, B, C... by default
+    fn col_header(&self, col: i32) -> String {
+        SmartTable::default_col_header(col)
+    }
+}
+
+// The model of the virtual mode, asking a closure for each cell
+struct VirtualModel<F> {
+    rows: i32,
+    cols: i32,
+    cell: F,
+}
+
+impl<F: Fn(i32, i32) -> String + Send> TableModel for VirtualModel<F> {
+    fn row_count(&self) -> i32 {
+        self.rows
+    }
+
+    fn col_count(&self) -> i32 {
+        self.cols
+    }
+
+    fn cell(&self, row: i32, col: i32) -> String {
+        (self.cell)(row, col)
+    }
+}
+
+impl SmartTable {
+    /// Display the model instead of the table's own data. The table is resized to the model,
+    /// call `refresh_model()` when its dimensions change.
+    /// Per-cell styling, row tags, bands and the data manipulation methods apply to the table's
+    /// own data and do nothing while a model is set, the cell accessors, copying, exporting
+    /// and printing read the model
+    pub fn set_model<M: TableModel + 'static>(&mut self, model: M) {
+        self.data.try_lock().unwrap().clear();
+        self.row_headers.try_lock().unwrap().clear();
+        self.col_headers.try_lock().unwrap().clear();
+        self.row_tags.try_lock().unwrap().clear();
+        self.tree.try_lock().unwrap().clear();
+        self.forget_positions();
+        *self.model.try_lock().unwrap() = Some(Box::new(model));
+        self.refresh_model();
+    }
+
+    /// Construct the table over a model, see `set_model()`
+    pub fn with_model<M: TableModel + 'static>(mut self, model: M) -> Self {
+        self.set_model(model);
+        self
+    }
+
+    /// Resize the table to the model's dimensions, reapplying the row filter, and redraw it
+    pub fn refresh_model(&mut self) {
+        let Some(cols) = self
+            .model
+            .try_lock()
+            .unwrap()
+            .as_ref()
+            .map(|m| m.col_count())
+        else {
+            return;
+        };
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .resize(cols as usize, ColProps::default());
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &self.data.try_lock().unwrap(),
+            &self.footer,
+        );
+        self.table.set_cols(cols + rtl as i32);
+        self.table.redraw();
+    }
+
+    /// Go back to displaying the table's own data, which is left empty
+    pub fn clear_model(&mut self) {
+        *self.model.try_lock().unwrap() = None;
+        self.forget_positions();
+        self.col_props.try_lock().unwrap().clear();
+        self.table.set_rows(0);
+        self.table
+            .set_cols(self.opts.try_lock().unwrap().rtl as i32);
+        self.table.redraw();
+    }
+
+    /// Whether a model is displayed
+    pub fn has_model(&self) -> bool {
+        self.model.try_lock().unwrap().is_some()
+    }
+
+    /// Virtual mode for large datasets: nothing is stored in the table, `cell` is called for the
+    /// visible cells when drawing. Call again to change the dimensions. The cells are read-only,
+    /// implement `TableModel` to accept edits
+    pub fn set_virtual<F: Fn(i32, i32) -> String + Send + 'static>(
+        &mut self,
+        rows: i32,
+        cols: i32,
+        cell: F,
+    ) {
+        self.set_model(VirtualModel { rows, cols, cell });
+    }
+}
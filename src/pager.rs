@@ -0,0 +1,96 @@
+// Paging, showing the rows a page at a time
+
+use super::SmartTable;
+use fltk::{
+    app, button, frame, group,
+    prelude::{GroupExt, WidgetBase, WidgetExt},
+};
+
+impl SmartTable {
+    /// Show the rows `n` at a time, `set_page()` picking the page shown.
+    /// Pages are made of the rows passing the row filter, 0 shows all the rows again
+    pub fn set_page_size(&mut self, n: i32) {
+        self.edit_filter(|f| f.page = (n > 0).then(|| (n, f.page.map_or(0, |p| p.1))));
+        self.refilter();
+        self.update_pager();
+    }
+
+    /// Returns the number of rows per page, 0 when all the rows are shown
+    pub fn page_size(&self) -> i32 {
+        self.paging().map_or(0, |(size, _)| size)
+    }
+
+    /// Show the page at the index, kept within the pages
+    pub fn set_page(&mut self, page: i32) {
+        if let Some(f) = self.filter.try_lock().unwrap().as_mut() {
+            if let Some(p) = f.page.as_mut() {
+                p.1 = page.max(0);
+            }
+        }
+        self.refilter();
+        self.update_pager();
+    }
+
+    /// Returns the index of the shown page
+    pub fn page(&self) -> i32 {
+        self.paging().map_or(0, |(_, page)| page)
+    }
+
+    /// Returns the number of pages, at least 1
+    pub fn page_count(&self) -> i32 {
+        let filter = self.filter.try_lock().unwrap();
+        match filter.as_ref().and_then(|f| Some((f.page?.0, f.passing))) {
+            Some((size, rows)) => ((rows + size - 1) / size).max(1),
+            None => 1,
+        }
+    }
+
+    fn paging(&self) -> Option<(i32, i32)> {
+        self.filter
+            .try_lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|f| f.page)
+    }
+
+    /// Create a bar with buttons going to the previous and next pages around a
+    /// "Page i of n" label, to place below the table
+    pub fn pager_bar(&mut self, x: i32, y: i32, w: i32, h: i32) -> group::Group {
+        let bar = group::Group::new(x, y, w, h, None);
+        let mut prev = button::Button::new(x, y, h, h, "@<");
+        let label = frame::Frame::new(x + h, y, w - 2 * h, h, None);
+        let mut next = button::Button::new(x + w - h, y, h, h, "@>");
+        bar.end();
+        prev.set_callback({
+            let mut this = self.clone();
+            move |_| this.set_page(this.page() - 1)
+        });
+        next.set_callback({
+            let mut this = self.clone();
+            move |_| this.set_page(this.page() + 1)
+        });
+        *self.pager.try_lock().unwrap() = Some(label);
+        self.update_pager();
+        bar
+    }
+
+    // Updates the label of the pager bar
+    pub(crate) fn update_pager(&self) {
+        let text = format!("Page {} of {}", self.page() + 1, self.page_count());
+        if let Some(label) = &mut *self.pager.try_lock().unwrap() {
+            if label.label() != text {
+                label.set_label(&text);
+                label.redraw();
+            }
+        }
+    }
+
+    // Updates the pager bar once the current event is handled, e.g. rows were appended
+    // while drawing
+    pub(crate) fn update_pager_later(&self) {
+        if self.pager.try_lock().is_ok_and(|p| p.is_some()) {
+            let this = self.clone();
+            app::add_timeout3(0.0, move |_| this.update_pager());
+        }
+    }
+}
@@ -0,0 +1,269 @@
+// A small regular expression matcher for finding cells: literals, `.`, `[...]` classes with
+// ranges and `^` negation, `\d`, `\w`, `\s` and their negations, the `*`, `+` and `?` repeats,
+// and the `^` and `$` anchors. Alternation, groups and counted repeats aren't supported
+
+#[derive(Debug, Clone)]
+enum Atom {
+    Any,
+    Char(char),
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Repeat {
+    One,
+    Optional,
+    Star,
+    Plus,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Regex {
+    nodes: Vec<(Atom, Repeat)>,
+    start: bool,
+    end: bool,
+    icase: bool,
+}
+
+fn chars_eq(a: char, b: char, icase: bool) -> bool {
+    a == b || (icase && a.to_lowercase().eq(b.to_lowercase()))
+}
+
+// Ranges of the \d, \w and \s classes
+fn escape_class(c: char) -> Option<Vec<(char, char)>> {
+    match c.to_ascii_lowercase() {
+        'd' => Some(vec![('0', '9')]),
+        'w' => Some(vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')]),
+        's' => Some(vec![(' ', ' '), ('\t', '\r')]),
+        _ => None,
+    }
+}
+
+impl Atom {
+    fn matches(&self, c: char, icase: bool) -> bool {
+        match self {
+            Atom::Any => true,
+            Atom::Char(a) => chars_eq(*a, c, icase),
+            Atom::Class { ranges, negated } => {
+                let inside = |c: char| ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c));
+                let found = inside(c)
+                    || (icase && (c.to_lowercase().any(inside) || c.to_uppercase().any(inside)));
+                found != *negated
+            }
+        }
+    }
+}
+
+impl Regex {
+    // Parses the pattern, None when it's invalid
+    pub(crate) fn new(pattern: &str, icase: bool) -> Option<Regex> {
+        let mut chars = pattern.chars().peekable();
+        let start = chars.next_if_eq(&'^').is_some();
+        let mut nodes: Vec<(Atom, Repeat)> = vec![];
+        let mut end = false;
+        while let Some(c) = chars.next() {
+            let atom = match c {
+                '$' if chars.peek().is_none() => {
+                    end = true;
+                    break;
+                }
+                '.' => Atom::Any,
+                '|' | '(' | ')' | '{' => return None,
+                '*' | '+' | '?' => {
+                    let last = nodes.last_mut().filter(|n| n.1 == Repeat::One)?;
+                    last.1 = match c {
+                        '*' => Repeat::Star,
+                        '+' => Repeat::Plus,
+                        _ => Repeat::Optional,
+                    };
+                    continue;
+                }
+                '\\' => {
+                    let e = chars.next()?;
+                    match escape_class(e) {
+                        Some(ranges) => Atom::Class {
+                            ranges,
+                            negated: e.is_ascii_uppercase(),
+                        },
+                        None => Atom::Char(e),
+                    }
+                }
+                '[' => {
+                    let negated = chars.next_if_eq(&'^').is_some();
+                    let mut ranges = vec![];
+                    loop {
+                        let lo = match chars.next()? {
+                            ']' if !ranges.is_empty() => break,
+                            '\\' => {
+                                let e = chars.next()?;
+                                if let Some(r) = escape_class(e).filter(|_| e.is_ascii_lowercase())
+                                {
+                                    ranges.extend(r);
+                                    continue;
+                                }
+                                e
+                            }
+                            c => c,
+                        };
+                        let hi = match chars.peek() {
+                            Some('-') => {
+                                chars.next();
+                                match chars.next()? {
+                                    // A trailing dash is literal
+                                    ']' => {
+                                        ranges.push((lo, lo));
+                                        ranges.push(('-', '-'));
+                                        break;
+                                    }
+                                    hi => hi,
+                                }
+                            }
+                            _ => lo,
+                        };
+                        ranges.push((lo, hi));
+                    }
+                    Atom::Class { ranges, negated }
+                }
+                c => Atom::Char(c),
+            };
+            nodes.push((atom, Repeat::One));
+        }
+        Some(Regex {
+            nodes,
+            start,
+            end,
+            icase,
+        })
+    }
+
+    // End of the match of the nodes from `node` on, starting at `pos` in the text.
+    // The outcome of each node and position is kept in `memo`, so backtracking
+    // never tries the same one twice
+    fn match_here(
+        &self,
+        node: usize,
+        pos: usize,
+        text: &[char],
+        memo: &mut [Option<Option<usize>>],
+    ) -> Option<usize> {
+        let Some((atom, repeat)) = self.nodes.get(node) else {
+            return (!self.end || pos == text.len()).then_some(pos);
+        };
+        let key = node * (text.len() + 1) + pos;
+        if let Some(end) = memo[key] {
+            return end;
+        }
+        let left = text.len() - pos;
+        let (min, max) = match repeat {
+            Repeat::One => (1, 1),
+            Repeat::Optional => (0, 1),
+            Repeat::Star => (0, left),
+            Repeat::Plus => (1, left),
+        };
+        let max = text[pos..]
+            .iter()
+            .take(max)
+            .take_while(|c| atom.matches(**c, self.icase))
+            .count();
+        // Greedy, backtracking to shorter repeats
+        let end = (min..=max)
+            .rev()
+            .find_map(|n| self.match_here(node + 1, pos + n, text, memo));
+        memo[key] = Some(end);
+        end
+    }
+
+    // The first match at or after `from`, as the start and end character indices
+    pub(crate) fn find_at(&self, text: &[char], from: usize) -> Option<(usize, usize)> {
+        let last = if self.start { 0 } else { text.len() };
+        let mut memo = vec![None; self.nodes.len() * (text.len() + 1)];
+        (from..=last).find_map(|s| self.match_here(0, s, text, &mut memo).map(|end| (s, end)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, text: &str) -> Option<(usize, usize)> {
+        let text: Vec<char> = text.chars().collect();
+        Regex::new(pattern, false)?.find_at(&text, 0)
+    }
+
+    #[test]
+    fn literals_and_any() {
+        assert_eq!(find("bc", "abcd"), Some((1, 3)));
+        assert_eq!(find("a.c", "xabcx"), Some((1, 4)));
+        assert_eq!(find("z", "abc"), None);
+        assert_eq!(find(r"\.", "a.b"), Some((1, 2)));
+    }
+
+    #[test]
+    fn repeats() {
+        assert_eq!(find("b+", "abbbc"), Some((1, 4)));
+        assert_eq!(find("colou?r", "color"), Some((0, 5)));
+        assert_eq!(find("colou?r", "colour"), Some((0, 6)));
+        assert_eq!(find("a.*z", "xabczz"), Some((1, 6)));
+        assert_eq!(find("x*", "abc"), Some((0, 0)));
+    }
+
+    #[test]
+    fn anchors() {
+        assert_eq!(find("^ab", "cab"), None);
+        assert_eq!(find("^ca", "cab"), Some((0, 2)));
+        assert_eq!(find("c$", "abc"), Some((2, 3)));
+        assert_eq!(find("a$", "abc"), None);
+        assert_eq!(find("^abc$", "abc"), Some((0, 3)));
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(find(r"\d+", "ab 123 c"), Some((3, 6)));
+        assert_eq!(find(r"\W", "ab-c"), Some((2, 3)));
+        assert_eq!(find(r"\s", "a\tb"), Some((1, 2)));
+        assert_eq!(find("[a-c]x", "dbx"), Some((1, 3)));
+        assert_eq!(find("[^0-9]", "12a"), Some((2, 3)));
+        assert_eq!(find(r"[\d_]+", "ab1_2c"), Some((2, 5)));
+        assert_eq!(find("[a-]", "x-"), Some((1, 2)));
+    }
+
+    #[test]
+    fn ignoring_case() {
+        let text: Vec<char> = "say Hello".chars().collect();
+        let re = Regex::new("hELLO", true).unwrap();
+        assert_eq!(re.find_at(&text, 0), Some((4, 9)));
+        let re = Regex::new("[a-z]+", true).unwrap();
+        assert_eq!(re.find_at(&text, 3), Some((4, 9)));
+        assert_eq!(find("hELLO", "say Hello"), None);
+    }
+
+    #[test]
+    fn from_offset() {
+        let text: Vec<char> = "abab".chars().collect();
+        let re = Regex::new("ab", false).unwrap();
+        assert_eq!(re.find_at(&text, 1), Some((2, 4)));
+        assert_eq!(re.find_at(&text, 3), None);
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        assert!(Regex::new("*a", false).is_none());
+        assert!(Regex::new("a+*", false).is_none());
+        assert!(Regex::new("[ab", false).is_none());
+        assert!(Regex::new("a\\", false).is_none());
+        assert!(Regex::new("a|b", false).is_none());
+        assert!(Regex::new("(ab)", false).is_none());
+        assert!(Regex::new("a{2}", false).is_none());
+        assert_eq!(find(r"\(a\)", "(a)"), Some((0, 3)));
+    }
+
+    #[test]
+    fn many_repeats() {
+        let text = "a".repeat(200);
+        assert_eq!(find(".*.*.*.*x", &text), None);
+        assert_eq!(find(".*.*.*a$", &text), Some((0, 200)));
+    }
+}
@@ -0,0 +1,67 @@
+// Draw timings, compiled in with the `perf` feature
+
+use super::SmartTable;
+use fltk::table::TableContext;
+use std::time::{Duration, Instant};
+
+/// Statistics of the table's drawing, see `SmartTable::perf_stats()`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PerfStats {
+    /// Number of frames drawn
+    pub frames: u64,
+    /// Time taken by the last frame
+    pub last_frame: Duration,
+    /// Longest frame so far
+    pub max_frame: Duration,
+    /// Cells painted in the last frame
+    pub cells_painted: u32,
+    /// Cells skipped in the last frame because the data was locked by another thread
+    pub cells_skipped: u32,
+    /// Time spent locking the data in the last frame
+    pub lock_wait: Duration,
+    frame_start: Option<Instant>,
+}
+
+impl PerfStats {
+    fn frame_start(&mut self) {
+        self.frame_start = Some(Instant::now());
+        self.cells_painted = 0;
+        self.cells_skipped = 0;
+        self.lock_wait = Duration::ZERO;
+    }
+
+    // Counts a draw_cell call, `locked` is whether the data could be locked to draw it
+    pub(crate) fn record(&mut self, ctx: TableContext, locked: bool, wait: Duration) {
+        if let TableContext::StartPage = ctx {
+            self.frame_start();
+        }
+        self.lock_wait += wait;
+        if let TableContext::Cell = ctx {
+            if locked {
+                self.cells_painted += 1;
+            } else {
+                self.cells_skipped += 1;
+            }
+        }
+    }
+
+    pub(crate) fn frame_end(&mut self) {
+        if let Some(start) = self.frame_start.take() {
+            self.frames += 1;
+            self.last_frame = start.elapsed();
+            self.max_frame = self.max_frame.max(self.last_frame);
+        }
+    }
+}
+
+impl SmartTable {
+    /// Get the drawing statistics
+    pub fn perf_stats(&self) -> PerfStats {
+        *self.perf.lock().unwrap()
+    }
+
+    /// Reset the drawing statistics
+    pub fn reset_perf_stats(&mut self) {
+        *self.perf.lock().unwrap() = PerfStats::default();
+    }
+}
@@ -0,0 +1,348 @@
+// Printing and the print preview dialog, both laid out by `SmartTable::paginate`
+
+use super::{RowBand, SmartTable};
+use fltk::{button, enums::*, frame, image, prelude::*, printer, surface::ImageSurface, window};
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Page layout used when printing the table, sizes are in points
+#[derive(Debug, Clone, Copy)]
+pub struct PrintOpts {
+    pub page_width: i32,
+    pub page_height: i32,
+    pub margin: i32,
+    /// Repeat the column headers at the top of every page
+    pub repeat_col_headers: bool,
+    /// Repeat the row headers beside every page
+    pub repeat_row_headers: bool,
+}
+
+impl Default for PrintOpts {
+    fn default() -> Self {
+        // A4 with half-inch margins
+        Self {
+            page_width: 595,
+            page_height: 842,
+            margin: 36,
+            repeat_col_headers: true,
+            repeat_row_headers: true,
+        }
+    }
+}
+
+/// A printed page, holding the ranges of data rows and columns it shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintPage {
+    pub rows: Range<i32>,
+    pub cols: Range<i32>,
+}
+
+// Splits the sizes into consecutive runs fitting within `avail`, every run holds at least one entry
+fn chunks(sizes: &[i32], avail: i32) -> Vec<Range<i32>> {
+    let mut out = vec![];
+    let mut start = 0;
+    let mut used = 0;
+    for (i, sz) in sizes.iter().enumerate() {
+        let i = i as i32;
+        if i > start && used + sz > avail {
+            out.push(start..i);
+            start = i;
+            used = 0;
+        }
+        used += sz;
+    }
+    if start < sizes.len() as i32 {
+        out.push(start..sizes.len() as i32);
+    }
+    out
+}
+
+impl SmartTable {
+    // Widths of the data columns, in data order
+    fn data_col_widths(&self) -> Vec<i32> {
+        (0..self.column_count())
+            .map(|c| self.col_width(c))
+            .collect()
+    }
+
+    /// Lays the table out on pages, going down the rows before moving across the columns
+    pub fn paginate(&self, opts: &PrintOpts) -> Vec<PrintPage> {
+        let hdr_w = if opts.repeat_row_headers {
+            self.row_header_width()
+        } else {
+            0
+        };
+        let hdr_h = if opts.repeat_col_headers {
+            self.col_header_height()
+        } else {
+            0
+        };
+        let heights: Vec<i32> = (0..self.row_count()).map(|r| self.row_height(r)).collect();
+        let row_chunks = chunks(&heights, opts.page_height - 2 * opts.margin - hdr_h);
+        let col_chunks = chunks(
+            &self.data_col_widths(),
+            opts.page_width - 2 * opts.margin - hdr_w,
+        );
+        col_chunks
+            .iter()
+            .flat_map(|cols| {
+                row_chunks.iter().map(|rows| PrintPage {
+                    rows: rows.clone(),
+                    cols: cols.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Draws a page on the current drawing surface, with its top left corner at x, y.
+    /// In right-to-left mode the page is mirrored within `width`
+    pub fn draw_page(&self, page: &PrintPage, x: i32, y: i32, width: i32, opts: &PrintOpts) {
+        let topts = *self.opts.try_lock().unwrap();
+        let filter = self.filter.try_lock().unwrap();
+        let data = self.data.try_lock().unwrap();
+        let model = self.model.try_lock().unwrap();
+        let model = model.as_deref();
+        let col_props = self.col_props.try_lock().unwrap();
+        let row_headers = self.row_headers.try_lock().unwrap();
+        let col_headers = self.col_headers.try_lock().unwrap();
+        let row_band = self.row_band.try_lock().unwrap();
+        let bands = match &*row_band {
+            Some(RowBand::Col(col, colors)) => Self::col_bands(&data, *col, colors),
+            _ => vec![],
+        };
+        let hdr_w = if opts.repeat_row_headers {
+            self.row_header_width()
+        } else {
+            0
+        };
+        let hdr_h = if opts.repeat_col_headers {
+            self.col_header_height()
+        } else {
+            0
+        };
+        // Horizontal position of a span starting at `cx` from the leading edge
+        let place = |cx: i32, w: i32| {
+            if topts.rtl {
+                x + width - cx - w
+            } else {
+                x + cx
+            }
+        };
+        if hdr_h > 0 {
+            let mut cx = hdr_w;
+            for c in page.cols.clone() {
+                let cw = self.col_width(c);
+                let header = match model {
+                    Some(m) => m.col_header(c),
+                    None => col_headers[c as usize].clone(),
+                };
+                Self::draw_header(&header, place(cx, cw), y, cw, hdr_h, &topts);
+                cx += cw;
+            }
+        }
+        let mut cy = y + hdr_h;
+        for r in page.rows.clone() {
+            let rh = self.row_height(r);
+            let r = Self::data_row(&filter, r);
+            if hdr_w > 0 {
+                let header = match model {
+                    Some(m) => m.row_header(r),
+                    None => row_headers[r as usize].clone(),
+                };
+                Self::draw_header(
+                    &header,
+                    place(0, hdr_w),
+                    cy,
+                    hdr_w,
+                    rh,
+                    &topts.row_headers(),
+                );
+            }
+            let band = match &*row_band {
+                Some(RowBand::Fn(f)) => data.get(r as usize).and_then(|row| f(r, row)),
+                _ => bands.get(r as usize).copied(),
+            };
+            let mut cx = hdr_w;
+            for c in page.cols.clone() {
+                let cw = self.col_width(c);
+                let cell = Self::source_cell(&data, model, r, c).unwrap_or_default();
+                Self::draw_data(
+                    &cell,
+                    &col_props[c as usize],
+                    place(cx, cw),
+                    cy,
+                    cw,
+                    rh,
+                    false,
+                    false,
+                    band,
+                    &topts,
+                );
+                cx += cw;
+            }
+            cy += rh;
+        }
+    }
+
+    /// Prints the table, showing the system's print dialog first.
+    /// Pages are shrunk to fit if the printer's printable area is smaller than the page layout
+    pub fn print(&self, opts: &PrintOpts) -> Result<(), FltkError> {
+        let pages = self.paginate(opts);
+        let mut printer = printer::Printer::default();
+        let (from, to) = printer.begin_job(pages.len() as i32)?;
+        let from = from.unwrap_or(1).max(1) as usize;
+        let to = to.unwrap_or(pages.len() as i32) as usize;
+        let (cw, ch) = (
+            opts.page_width - 2 * opts.margin,
+            opts.page_height - 2 * opts.margin,
+        );
+        for page in pages.iter().take(to).skip(from - 1) {
+            printer.begin_page()?;
+            let (pw, ph) = printer.printable_rect();
+            let factor = (pw as f32 / cw as f32).min(ph as f32 / ch as f32).min(1.0);
+            if factor < 1.0 {
+                printer.scale(factor, factor);
+            }
+            self.draw_page(page, 0, 0, cw, opts);
+            printer.end_page()?;
+        }
+        printer.end_job();
+        Ok(())
+    }
+
+    // Renders a whole page, margins included, into an image
+    fn page_image(&self, page: &PrintPage, opts: &PrintOpts) -> Option<image::RgbImage> {
+        let surf = ImageSurface::new(opts.page_width, opts.page_height, false);
+        ImageSurface::push_current(&surf);
+        fltk::draw::draw_rect_fill(0, 0, opts.page_width, opts.page_height, Color::White);
+        self.draw_page(
+            page,
+            opts.margin,
+            opts.margin,
+            opts.page_width - 2 * opts.margin,
+            opts,
+        );
+        ImageSurface::pop_current();
+        surf.image()
+    }
+}
+
+/// A dialog previewing the printed pages, with page navigation, zoom and a button to print
+#[derive(Debug)]
+pub struct PrintPreview {
+    win: window::Window,
+}
+
+impl PrintPreview {
+    /// Creates the preview window for the table, rendering its pages with the given layout
+    pub fn new(table: &SmartTable, opts: PrintOpts) -> Self {
+        let pages: Vec<image::RgbImage> = table
+            .paginate(&opts)
+            .iter()
+            .filter_map(|p| table.page_image(p, &opts))
+            .collect();
+        // Current page and zoom
+        let state = Rc::new(RefCell::new((0usize, 1.0f32)));
+
+        let mut win = window::Window::default()
+            .with_size(opts.page_width + 20, opts.page_height + 60)
+            .with_label("Print Preview");
+        let mut prev = button::Button::new(10, 10, 30, 30, "@<");
+        let mut next = button::Button::new(45, 10, 30, 30, "@>");
+        let label = frame::Frame::new(80, 10, 100, 30, None);
+        let mut zoom_out = button::Button::new(185, 10, 30, 30, "-");
+        let mut zoom_in = button::Button::new(220, 10, 30, 30, "+");
+        let mut print = button::Button::new(win.w() - 90, 10, 80, 30, "Print");
+        let scroll = fltk::group::Scroll::new(10, 50, win.w() - 20, win.h() - 60, None);
+        let view = frame::Frame::new(10, 50, opts.page_width, opts.page_height, None);
+        scroll.end();
+        win.end();
+        win.resizable(&scroll);
+        win.make_modal(true);
+
+        let page_count = pages.len();
+        let show_page = Rc::new({
+            let (label, scroll, view) = (label.clone(), scroll.clone(), view.clone());
+            let state = state.clone();
+            move || {
+                let (mut label, mut scroll, mut view) =
+                    (label.clone(), scroll.clone(), view.clone());
+                let (idx, zoom) = *state.borrow();
+                label.set_label(&format!("Page {} of {}", idx + 1, pages.len()));
+                if let Some(img) = pages.get(idx) {
+                    let mut img = img.clone();
+                    let (w, h) = (
+                        (opts.page_width as f32 * zoom) as i32,
+                        (opts.page_height as f32 * zoom) as i32,
+                    );
+                    img.scale(w, h, true, true);
+                    view.resize(scroll.x(), scroll.y(), w, h);
+                    view.set_image(Some(img));
+                }
+                scroll.scroll_to(0, 0);
+                scroll.redraw();
+            }
+        });
+        show_page();
+
+        prev.set_callback({
+            let (state, show_page) = (state.clone(), show_page.clone());
+            move |_| {
+                let idx = state.borrow().0;
+                if idx > 0 {
+                    state.borrow_mut().0 = idx - 1;
+                    show_page();
+                }
+            }
+        });
+        next.set_callback({
+            let (state, show_page) = (state.clone(), show_page.clone());
+            move |_| {
+                let idx = state.borrow().0;
+                if idx + 1 < page_count {
+                    state.borrow_mut().0 = idx + 1;
+                    show_page();
+                }
+            }
+        });
+        zoom_out.set_callback({
+            let (state, show_page) = (state.clone(), show_page.clone());
+            move |_| {
+                let zoom = state.borrow().1;
+                state.borrow_mut().1 = (zoom / 1.25).max(0.25);
+                show_page();
+            }
+        });
+        zoom_in.set_callback({
+            let (state, show_page) = (state.clone(), show_page.clone());
+            move |_| {
+                let zoom = state.borrow().1;
+                state.borrow_mut().1 = (zoom * 1.25).min(4.0);
+                show_page();
+            }
+        });
+        print.set_callback({
+            let table = table.clone();
+            move |b| {
+                if table.print(&opts).is_ok() {
+                    if let Some(mut win) = b.window() {
+                        win.hide();
+                    }
+                }
+            }
+        });
+
+        Self { win }
+    }
+
+    /// Shows the preview window
+    pub fn show(&mut self) {
+        self.win.show();
+    }
+
+    /// Returns whether the preview window is shown
+    pub fn shown(&self) -> bool {
+        self.win.shown()
+    }
+}
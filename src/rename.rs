@@ -0,0 +1,126 @@
+// Renaming the column headers inline, compiled in with the `editing` feature
+
+use super::{FocusLossPolicy, SmartTable};
+use fltk::{
+    app,
+    enums::*,
+    input,
+    prelude::{GroupExt, InputExt, TableExt, WidgetBase, WidgetExt},
+    table, window,
+};
+
+pub(crate) type HeaderRenamedCallback = Box<dyn FnMut(i32, &str, &str) + Send>;
+
+impl SmartTable {
+    // Creates the input used to rename the headers
+    pub(crate) fn setup_header_editor(&mut self) {
+        let mut inp = input::Input::default();
+        inp.set_trigger(CallbackTrigger::EnterKey);
+        let win =
+            window::Window::from_dyn_widget_ptr(self.table.top_window().unwrap().as_widget_ptr());
+        win.unwrap().add(&inp);
+        inp.hide();
+        inp.handle({
+            let mut table = self.table.clone();
+            let opts = self.opts.clone();
+            move |i, ev| match ev {
+                Event::KeyUp if app::event_key() == Key::Escape => {
+                    i.hide();
+                    table.take_focus().ok();
+                    true
+                }
+                // Follows the cell editor's options when the focus moves to another widget
+                Event::Unfocus if i.visible() => {
+                    let Some(mut next) = app::focus() else {
+                        return false;
+                    };
+                    let policy = opts.try_lock().unwrap().edit_focus_loss;
+                    match policy {
+                        FocusLossPolicy::Commit => i.do_callback(),
+                        FocusLossPolicy::Cancel => i.hide(),
+                    }
+                    next.take_focus().ok();
+                    false
+                }
+                _ => false,
+            }
+        });
+        self.header_inp = Some(inp);
+    }
+
+    /// Set a callback called with the column, the old and the new name of a renamed header
+    pub fn set_on_header_renamed<F: FnMut(i32, &str, &str) + Send + 'static>(&mut self, cb: F) {
+        *self.on_header_renamed.try_lock().unwrap() = Some(Box::new(cb));
+    }
+
+    /// Open an editor over the column's header to rename it, Enter commits and Escape cancels.
+    /// Also done by double-clicking the header when `TableOpts::rename_headers` is set.
+    /// Returns false when the header isn't shown or comes from a model
+    pub fn edit_col_header(&mut self, col: i32) -> bool {
+        if self.model.try_lock().unwrap().is_some() || !self.is_col_visible(col) {
+            return false;
+        }
+        let rtl = self.opts.try_lock().unwrap().rtl;
+        let view_col = Self::display_col(rtl, self.column_count(), col);
+        let Some((x, y, w, h)) = self
+            .table
+            .find_cell(table::TableContext::ColHeader, 0, view_col)
+        else {
+            return false;
+        };
+        let Some(mut inp) = self.header_inp.clone() else {
+            return false;
+        };
+        inp.set_callback({
+            let mut this = self.clone();
+            move |i| {
+                let old = this.col_header_value(col);
+                let new = i.value();
+                i.hide();
+                this.table.take_focus().ok();
+                if new == old {
+                    return;
+                }
+                this.set_col_header_value(col, &new);
+                this.table.redraw();
+                let cb = this.on_header_renamed.clone();
+                if let Some(cb) = &mut *cb.try_lock().unwrap() {
+                    cb(col, &old, &new);
+                };
+            }
+        });
+        inp.resize(x, y, w, h);
+        inp.set_value(&self.col_headers.try_lock().unwrap()[col as usize]);
+        inp.show();
+        inp.take_focus().ok();
+        let end = inp.value().len() as i32;
+        inp.set_position(0).ok();
+        inp.set_mark(end).ok();
+        inp.redraw();
+        true
+    }
+
+    // Renames the header under a double-click
+    pub(crate) fn handle_header_rename(
+        &mut self,
+        t: &table::TableRow,
+        ev: Event,
+        rtl: bool,
+    ) -> bool {
+        if ev != Event::Released
+            || app::event_clicks_num() == 0
+            || app::event_mouse_button() != app::MouseButton::Left
+        {
+            return false;
+        }
+        let Some((table::TableContext::ColHeader, _, col, table::TableResizeFlag::None)) =
+            t.cursor2rowcol()
+        else {
+            return false;
+        };
+        let Some(col) = Self::data_col(rtl, t.cols() - rtl as i32, col) else {
+            return false;
+        };
+        self.edit_col_header(col)
+    }
+}
@@ -0,0 +1,170 @@
+// Row sorting, by clicking the column headers or programmatically
+
+use super::{tree::sibling_order, Cell, SmartTable, TableOpts};
+use fltk::{app, draw, enums::Event, prelude::*, table};
+use std::cmp::Ordering;
+
+/// Sort order of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    fn toggled(self) -> Self {
+        match self {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
+}
+
+pub(crate) type SortCallback = Box<dyn FnMut(i32, SortOrder) + Send>;
+
+#[derive(Default)]
+pub(crate) struct SortState {
+    sortable: bool,
+    // The sorted column and its order, drawn as an arrow in its header
+    pub(crate) current: Option<(i32, SortOrder)>,
+    on_changed: Option<SortCallback>,
+}
+
+impl SmartTable {
+    /// Sort the rows when a column header is clicked, clicking it again reverses the order
+    pub fn set_sortable(&mut self, flag: bool) {
+        self.sort.try_lock().unwrap().sortable = flag;
+    }
+
+    /// Set a callback called with the column and order when the rows get sorted
+    pub fn set_on_sort_changed<F: FnMut(i32, SortOrder) + Send + 'static>(&mut self, cb: F) {
+        self.sort.try_lock().unwrap().on_changed = Some(Box::new(cb));
+    }
+
+    /// Returns the sorted column and its order
+    pub fn sort_state(&self) -> Option<(i32, SortOrder)> {
+        self.sort.try_lock().unwrap().current
+    }
+
+    // Compares numbers, typed or not, by value and anything else as text
+    fn compare_cells(a: &Cell, b: &Cell) -> Ordering {
+        match (a.as_f64(), b.as_f64()) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.label.cmp(&b.label),
+        }
+    }
+
+    /// Sort the rows by the column, numbers by value and anything else as text.
+    /// Row headers and tags move along with their rows, child rows are sorted under their parent
+    pub fn sort_by_col(&mut self, col: i32, order: SortOrder) {
+        self.sort_rows(col, order, Self::compare_cells);
+    }
+
+    /// Sort the rows by the column using a comparator of the cell texts.
+    /// Row headers and tags move along with their rows
+    pub fn sort_by<F: FnMut(&str, &str) -> Ordering>(&mut self, col: i32, mut cmp: F) {
+        self.sort_rows(col, SortOrder::Ascending, |a, b| cmp(&a.label, &b.label));
+    }
+
+    // Reorders the rows by the column, moving their headers, tags and tree nodes along
+    fn sort_rows<F: FnMut(&Cell, &Cell) -> Ordering>(
+        &mut self,
+        col: i32,
+        order: SortOrder,
+        mut cmp: F,
+    ) {
+        if self.model.try_lock().unwrap().is_some() {
+            return;
+        }
+        self.forget_positions();
+        let mut data = self.data.try_lock().unwrap();
+        let mut row_headers = self.row_headers.try_lock().unwrap();
+        let mut row_tags = self.row_tags.try_lock().unwrap();
+        let mut tree = self.tree.try_lock().unwrap();
+        // Tree rows are sorted among their siblings, keeping the rows nested under them
+        let idx = sibling_order(&tree, data.len(), |a, b| {
+            let ord = cmp(&data[a][col as usize], &data[b][col as usize]);
+            match order {
+                SortOrder::Ascending => ord,
+                SortOrder::Descending => ord.reverse(),
+            }
+        });
+        *data = idx.iter().map(|&i| data[i].clone()).collect();
+        *row_headers = idx.iter().map(|&i| row_headers[i].clone()).collect();
+        *row_tags = idx.iter().map(|&i| row_tags[i].clone()).collect();
+        *tree = idx.iter().map(|&i| tree[i]).collect();
+        drop(tree);
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop((data, row_headers, row_tags));
+        let mut sort = self.sort.try_lock().unwrap();
+        sort.current = Some((col, order));
+        // Taken out of the sort state while running, so it can query or sort the table
+        let cb = sort.on_changed.take();
+        drop(sort);
+        self.mark_changed();
+        self.table.redraw();
+        if let Some(mut cb) = cb {
+            cb(col, order);
+            self.sort.try_lock().unwrap().on_changed.get_or_insert(cb);
+        }
+    }
+
+    // Sorts by a clicked column header, returns whether the click was handled
+    pub(crate) fn handle_sort_click(&mut self, t: &table::TableRow, ev: Event, rtl: bool) -> bool {
+        if ev != Event::Released
+            || !app::event_is_click()
+            || app::event_mouse_button() != app::MouseButton::Left
+        {
+            return false;
+        }
+        let Some((table::TableContext::ColHeader, _, col, table::TableResizeFlag::None)) =
+            t.cursor2rowcol()
+        else {
+            return false;
+        };
+        let sort = self.sort.try_lock().unwrap();
+        if !sort.sortable {
+            return false;
+        }
+        let Some(col) = Self::data_col(rtl, t.cols() - rtl as i32, col) else {
+            return false;
+        };
+        let order = match sort.current {
+            Some((c, order)) if c == col => order.toggled(),
+            _ => SortOrder::Ascending,
+        };
+        drop(sort);
+        self.sort_by_col(col, order);
+        true
+    }
+
+    // Draws the arrow of the sorted column at the right of its header
+    pub(crate) fn draw_sort_arrow(
+        order: SortOrder,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        opts: &TableOpts,
+    ) {
+        let s = (opts.header_font_size / 3).max(3);
+        let (cx, cy) = (x + w - 2 * s, y + h / 2);
+        draw::set_draw_color(opts.header_font_color);
+        match order {
+            SortOrder::Ascending => {
+                draw::draw_polygon(cx - s, cy + s / 2, cx + s, cy + s / 2, cx, cy - s / 2)
+            }
+            SortOrder::Descending => {
+                draw::draw_polygon(cx - s, cy - s / 2, cx + s, cy - s / 2, cx, cy + s / 2)
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// Tooltips of the cells, shown when hovering them
+
+use super::{ColProps, SmartTable};
+use fltk::{misc::Tooltip, prelude::TableExt, table};
+use std::ffi::{CStr, CString};
+
+pub(crate) type TooltipProvider = Box<dyn Fn(i32, i32) -> Option<String> + Send>;
+
+#[derive(Default)]
+pub(crate) struct Tooltips {
+    provider: Option<TooltipProvider>,
+    // FLTK keeps a pointer to the text of the shown tooltip, so it's leaked until another
+    // text replaces it
+    shown: Option<&'static CStr>,
+}
+
+impl Tooltips {
+    fn show(&mut self, t: &table::TableRow, (x, y, w, h): (i32, i32, i32, i32), text: &str) {
+        let text = text.replace('\0', "");
+        match self.shown {
+            Some(tip) if tip.to_bytes() == text.as_bytes() => {
+                Tooltip::enter_area(t, x, y, w, h, tip);
+            }
+            old => {
+                let tip: &'static CStr = Box::leak(CString::new(text).unwrap().into_boxed_c_str());
+                Tooltip::enter_area(t, x, y, w, h, tip);
+                self.shown = Some(tip);
+                if let Some(old) = old {
+                    // SAFETY: the old text was leaked from a box above, and FLTK now points
+                    // at the new one
+                    drop(unsafe { Box::from_raw(old as *const CStr as *mut CStr) });
+                }
+            }
+        }
+    }
+}
+
+impl SmartTable {
+    /// Set the tooltip shown when hovering the cell, an empty text removes it
+    pub fn set_cell_tooltip(&mut self, row: i32, col: i32, text: &str) {
+        if self.has_model() {
+            return;
+        }
+        self.data.try_lock().unwrap()[row as usize][col as usize].tooltip =
+            (!text.is_empty()).then(|| text.to_string());
+    }
+
+    /// Set a function giving the tooltip of the hovered cell from its row and column,
+    /// for the cells without a tooltip of their own
+    pub fn set_tooltip_provider<F: Fn(i32, i32) -> Option<String> + Send + 'static>(
+        &mut self,
+        f: F,
+    ) {
+        self.tooltips.try_lock().unwrap().provider = Some(Box::new(f));
+    }
+
+    // Shows the tooltip of the column header under the mouse
+    pub(crate) fn header_tooltip(&self, t: &table::TableRow, col_props: &[ColProps], rtl: bool) {
+        if let Some((table::TableContext::ColHeader, _, col, _)) = t.cursor2rowcol() {
+            let tip = Self::data_col(rtl, col_props.len() as _, col)
+                .and_then(|c| col_props.get(c as usize)?.tooltip.as_ref());
+            if let (Some(tip), Some(rect)) =
+                (tip, t.find_cell(table::TableContext::ColHeader, 0, col))
+            {
+                self.tooltips.try_lock().unwrap().show(t, rect, tip);
+            }
+        }
+    }
+
+    // Shows the tooltip of the cell under the mouse
+    pub(crate) fn cell_tooltip(&self, t: &table::TableRow, rtl: bool) {
+        let Some((table::TableContext::Cell, view_row, view_col, _)) = t.cursor2rowcol() else {
+            return;
+        };
+        let Some(col) = Self::data_col(rtl, self.column_count(), view_col) else {
+            return;
+        };
+        let row = self.view_to_data_row(view_row);
+        let own = self
+            .data
+            .try_lock()
+            .ok()
+            .and_then(|d| d.get(row as usize)?.get(col as usize)?.tooltip.clone());
+        let mut tooltips = self.tooltips.try_lock().unwrap();
+        let tip = own.or_else(|| tooltips.provider.as_ref()?(row, col));
+        if let (Some(tip), Some(rect)) = (
+            tip,
+            t.find_cell(table::TableContext::Cell, view_row, view_col),
+        ) {
+            tooltips.show(t, rect, &tip);
+        }
+    }
+}
@@ -0,0 +1,332 @@
+// Tree rows, nesting rows under a parent row which folds them away when collapsed
+
+use super::{SmartTable, TableOpts};
+use fltk::{
+    app, draw,
+    enums::*,
+    prelude::{TableExt, WidgetExt},
+    table,
+};
+use std::{cmp::Ordering, ops::Range};
+
+// Width of a level of indentation, the triangle of a parent row is centered in its level
+const INDENT: i32 = 16;
+
+// A row's depth in the tree and whether its children show
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TreeNode {
+    level: i32,
+    expanded: bool,
+}
+
+impl Default for TreeNode {
+    fn default() -> Self {
+        Self {
+            level: 0,
+            expanded: true,
+        }
+    }
+}
+
+// The node of each data row, kept in step with the rows. Rows without one are expanded
+// top level rows
+pub(crate) type TreeNodes = Vec<Option<TreeNode>>;
+
+pub(crate) type ExpandCallback = Box<dyn FnMut(&mut SmartTable, i32, bool) + Send>;
+
+fn node(nodes: &TreeNodes, row: i32) -> TreeNode {
+    nodes
+        .get(row as usize)
+        .copied()
+        .flatten()
+        .unwrap_or_default()
+}
+
+// The children of a row follow it one level deeper
+fn has_children(nodes: &TreeNodes, row: i32) -> bool {
+    row + 1 < nodes.len() as i32 && node(nodes, row + 1).level > node(nodes, row).level
+}
+
+// Orders the rows with each parent's children sorted among themselves, every row moving
+// with the rows nested under it. Rows outside the tree are top level siblings
+pub(crate) fn sibling_order(
+    nodes: &TreeNodes,
+    rows: usize,
+    mut cmp: impl FnMut(usize, usize) -> Ordering,
+) -> Vec<usize> {
+    fn sort_range(
+        nodes: &TreeNodes,
+        range: Range<usize>,
+        cmp: &mut dyn FnMut(usize, usize) -> Ordering,
+        order: &mut Vec<usize>,
+    ) {
+        let mut blocks = vec![];
+        let mut row = range.start;
+        while row < range.end {
+            let level = node(nodes, row as i32).level;
+            let mut end = row + 1;
+            while end < range.end && node(nodes, end as i32).level > level {
+                end += 1;
+            }
+            blocks.push(row..end);
+            row = end;
+        }
+        // A stable sort, siblings comparing equal keep their order
+        blocks.sort_by(|a, b| cmp(a.start, b.start));
+        for block in blocks {
+            order.push(block.start);
+            sort_range(nodes, block.start + 1..block.end, cmp, order);
+        }
+    }
+    let mut order = Vec::with_capacity(rows);
+    sort_range(nodes, 0..rows, &mut cmp, &mut order);
+    order
+}
+
+impl SmartTable {
+    /// Insert a row of values as the last child of the parent row, returning its data row.
+    /// Parent rows get a triangle in the first column expanding and collapsing their children,
+    /// which are indented by their level. Returns -1 with a model set, whose rows the table doesn't own
+    pub fn add_child_row(&mut self, parent: i32, vals: &[&str]) -> i32 {
+        if self.has_model() {
+            return -1;
+        }
+        let nodes = self.tree.try_lock().unwrap();
+        let level = node(&nodes, parent).level + 1;
+        let mut row = parent + 1;
+        while row < nodes.len() as i32 && node(&nodes, row).level >= level {
+            row += 1;
+        }
+        drop(nodes);
+        self.insert_row(row, &self.row_label(row), vals);
+        self.tree.try_lock().unwrap()[row as usize] = Some(TreeNode {
+            level,
+            expanded: true,
+        });
+        self.sync_tree();
+        row
+    }
+
+    /// Returns the row's level in the tree, 0 for the top level rows
+    pub fn row_level(&self, row: i32) -> i32 {
+        node(&self.tree.try_lock().unwrap(), row).level
+    }
+
+    /// Set the row's level in the tree, e.g. to build a tree from rows appended with each
+    /// child following its parent
+    pub fn set_row_level(&mut self, row: i32, level: i32) {
+        if self.has_model() {
+            return;
+        }
+        let mut nodes = self.tree.try_lock().unwrap();
+        let expanded = node(&nodes, row).expanded;
+        nodes[row as usize] = Some(TreeNode {
+            level: level.max(0),
+            expanded,
+        });
+        drop(nodes);
+        self.sync_tree();
+    }
+
+    /// Returns whether rows are nested under the row
+    pub fn has_child_rows(&self, row: i32) -> bool {
+        has_children(&self.tree.try_lock().unwrap(), row)
+    }
+
+    /// Expand or collapse the row, a collapsed row hides all the rows nested under it
+    pub fn set_row_expanded(&mut self, row: i32, expanded: bool) {
+        if self.has_model() {
+            return;
+        }
+        let mut nodes = self.tree.try_lock().unwrap();
+        let level = node(&nodes, row).level;
+        nodes[row as usize] = Some(TreeNode { level, expanded });
+        drop(nodes);
+        self.sync_tree();
+    }
+
+    /// Returns whether the row is expanded, rows are expanded initially
+    pub fn is_row_expanded(&self, row: i32) -> bool {
+        node(&self.tree.try_lock().unwrap(), row).expanded
+    }
+
+    /// Expand all the rows
+    pub fn expand_all(&mut self) {
+        self.set_all_expanded(true);
+    }
+
+    /// Collapse all the rows, only the top level rows stay shown
+    pub fn collapse_all(&mut self) {
+        self.set_all_expanded(false);
+    }
+
+    fn set_all_expanded(&mut self, expanded: bool) {
+        for n in self.tree.try_lock().unwrap().iter_mut().flatten() {
+            n.expanded = expanded;
+        }
+        self.sync_tree();
+    }
+
+    /// Set a callback called when a row is expanded or collapsed by clicking its triangle,
+    /// given the data row and whether it's now expanded
+    pub fn set_on_row_expanded<F: FnMut(&mut SmartTable, i32, bool) + Send + 'static>(
+        &mut self,
+        cb: F,
+    ) {
+        *self.on_row_expanded.try_lock().unwrap() = Some(Box::new(cb));
+    }
+
+    // Folds away the rows under the collapsed rows and syncs the displayed rows
+    fn sync_tree(&mut self) {
+        let collapsed = self
+            .tree
+            .try_lock()
+            .unwrap()
+            .iter()
+            .flatten()
+            .any(|n| !n.expanded);
+        let tree = self.tree.clone();
+        self.edit_filter(|f| f.tree = collapsed.then_some(tree));
+        let data = self.data.try_lock().unwrap();
+        Self::sync_rows(
+            &mut self.table,
+            &self.filter,
+            &self.model,
+            &data,
+            &self.footer,
+        );
+        drop(data);
+        self.table.redraw();
+    }
+
+    // Whether each of the rows is folded away under a collapsed row
+    pub(crate) fn folded_rows(nodes: &TreeNodes, rows: usize) -> Vec<bool> {
+        let mut folded = vec![false; rows];
+        // Level of the collapsed row whose descendants are folded
+        let mut fold_below = None;
+        for (row, folded) in folded.iter_mut().enumerate() {
+            let n = node(nodes, row as i32);
+            if fold_below.is_some_and(|level| n.level > level) {
+                *folded = true;
+            } else {
+                fold_below = (!n.expanded).then_some(n.level);
+            }
+        }
+        folded
+    }
+
+    // Draws the triangle of a parent row at its level, returns the width of the indentation
+    // left of the cell's text
+    pub(crate) fn draw_branch(
+        nodes: &TreeNodes,
+        row: i32,
+        x: i32,
+        y: i32,
+        h: i32,
+        opts: &TableOpts,
+    ) -> i32 {
+        let n = node(nodes, row);
+        if has_children(nodes, row) {
+            let (cx, cy) = (x + n.level * INDENT + INDENT / 2, y + h / 2);
+            let s = INDENT / 4;
+            draw::set_draw_color(opts.cell_font_color);
+            if n.expanded {
+                draw::draw_polygon(cx - s, cy - s / 2, cx + s, cy - s / 2, cx, cy + s);
+            } else {
+                draw::draw_polygon(cx - s / 2, cy - s, cx - s / 2, cy + s, cx + s, cy);
+            }
+        }
+        (n.level + 1) * INDENT
+    }
+
+    // Expands or collapses a parent row when its triangle is clicked
+    pub(crate) fn handle_tree_click(&mut self, t: &table::TableRow, ev: Event, rtl: bool) -> bool {
+        if ev != Event::Push || app::event_mouse_button() != app::MouseButton::Left {
+            return false;
+        }
+        let Some((table::TableContext::Cell, view_row, view_col, _)) = t.cursor2rowcol() else {
+            return false;
+        };
+        if Self::data_col(rtl, self.column_count(), view_col) != Some(0) {
+            return false;
+        }
+        let row = self.view_to_data_row(view_row);
+        let nodes = self.tree.try_lock().unwrap();
+        let n = node(&nodes, row);
+        if !has_children(&nodes, row) {
+            return false;
+        }
+        drop(nodes);
+        let Some((x, ..)) = t.find_cell(table::TableContext::Cell, view_row, view_col) else {
+            return false;
+        };
+        let bx = x + n.level * INDENT;
+        if !(bx..bx + INDENT).contains(&app::event_x()) {
+            return false;
+        }
+        self.set_row_expanded(row, !n.expanded);
+        // Taken out of its slot while running, so it can expand rows or replace itself
+        let Some(mut cb) = self.on_row_expanded.try_lock().unwrap().take() else {
+            return true;
+        };
+        cb(self, row, !n.expanded);
+        self.on_row_expanded.try_lock().unwrap().get_or_insert(cb);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{filter::RowFilter, Cell};
+    use std::sync::{Arc, Mutex};
+
+    // The nodes of rows at the levels, the rows in `collapsed` are collapsed
+    fn nodes(levels: &[i32], collapsed: &[usize]) -> TreeNodes {
+        let node = |(row, &level)| {
+            Some(TreeNode {
+                level,
+                expanded: !collapsed.contains(&row),
+            })
+        };
+        levels.iter().enumerate().map(node).collect()
+    }
+
+    #[test]
+    fn folded() {
+        let n = nodes(&[0, 1, 2, 1, 0, 1], &[1]);
+        let folded = SmartTable::folded_rows(&n, 6);
+        assert_eq!(folded, [false, false, true, false, false, false]);
+        let n = nodes(&[0, 1, 2, 1], &[0, 1]);
+        let folded = SmartTable::folded_rows(&n, 4);
+        assert_eq!(folded, [false, true, true, true]);
+        // Rows without a node are expanded top level rows
+        let n = nodes(&[0, 1], &[0]);
+        let folded = SmartTable::folded_rows(&n, 4);
+        assert_eq!(folded, [false, true, false, false]);
+    }
+
+    #[test]
+    fn folded_rows_leave_the_view() {
+        let data: Vec<Vec<Cell>> = (0..5).map(|_| vec![Cell::default()]).collect();
+        let tree = Arc::new(Mutex::new(nodes(&[0, 1, 0, 1, 2], &[3])));
+        let mut f = RowFilter::default();
+        f.tree = Some(tree.clone());
+        f.update_view(&data, None);
+        assert_eq!(f.view, [0, 1, 2, 3]);
+        *tree.lock().unwrap() = nodes(&[0, 1, 0, 1, 2], &[0]);
+        f.update_view(&data, None);
+        assert_eq!(f.view, [0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn siblings_sorted_with_their_children() {
+        let n = nodes(&[0, 1, 1, 0, 1], &[]);
+        let keys = ["b", "b.2", "b.1", "a", "a.1"];
+        let order = sibling_order(&n, 5, |a, b| keys[a].cmp(keys[b]));
+        assert_eq!(order, [3, 4, 0, 2, 1]);
+        // Siblings comparing equal keep their order
+        let order = sibling_order(&n, 5, |_, _| Ordering::Equal);
+        assert_eq!(order, [0, 1, 2, 3, 4]);
+    }
+}
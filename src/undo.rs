@@ -0,0 +1,207 @@
+// Undo and redo of cell edits
+
+use super::SmartTable;
+use fltk::prelude::*;
+use std::collections::VecDeque;
+
+// A cell edit, with the values before and after it
+struct Edit {
+    row: i32,
+    col: i32,
+    old: String,
+    new: String,
+}
+
+// Each step holds the edits undone together
+#[derive(Default)]
+pub(crate) struct UndoStack {
+    undo: VecDeque<Vec<Edit>>,
+    redo: Vec<Vec<Edit>>,
+}
+
+impl UndoStack {
+    // Records an edit, dropping the oldest steps beyond `depth`. A new edit clears the redo stack
+    pub(crate) fn push(&mut self, row: i32, col: i32, old: String, new: String, depth: usize) {
+        self.push_step(vec![(row, col, old, new)], depth);
+    }
+
+    // Records edits of (row, col, old, new) as one step
+    pub(crate) fn push_step(&mut self, edits: Vec<(i32, i32, String, String)>, depth: usize) {
+        self.redo.clear();
+        let step: Vec<Edit> = edits
+            .into_iter()
+            .filter(|(_, _, old, new)| old != new)
+            .map(|(row, col, old, new)| Edit { row, col, old, new })
+            .collect();
+        if depth == 0 || step.is_empty() {
+            return;
+        }
+        self.undo.push_back(step);
+        while self.undo.len() > depth {
+            self.undo.pop_front();
+        }
+    }
+
+    // Moves the last step to the redo stack, returning its cells with their values before it,
+    // the last edit first
+    pub(crate) fn undo(&mut self) -> Option<Vec<(i32, i32, String)>> {
+        let step = self.undo.pop_back()?;
+        let cells = step.iter().rev().map(|e| (e.row, e.col, e.old.clone()));
+        let cells = cells.collect();
+        self.redo.push(step);
+        Some(cells)
+    }
+
+    // Moves the last undone step back, returning its cells with their values after it
+    pub(crate) fn redo(&mut self) -> Option<Vec<(i32, i32, String)>> {
+        let step = self.redo.pop()?;
+        let cells = step.iter().map(|e| (e.row, e.col, e.new.clone())).collect();
+        self.undo.push_back(step);
+        Some(cells)
+    }
+
+    // Forgets all the steps
+    pub(crate) fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl SmartTable {
+    /// Revert the last cell edit made with the editor or `set_cell_value()`, also bound to Ctrl+Z.
+    /// Returns whether there was an edit to undo. Sorting, or inserting, removing or moving rows
+    /// and columns clears the history
+    pub fn undo(&mut self) -> bool {
+        let Some(cells) = self.undo_stack.try_lock().unwrap().undo() else {
+            return false;
+        };
+        for (row, col, val) in cells {
+            if self.in_bounds(row, col) {
+                self.store_cell(row, col, &val);
+            }
+        }
+        self.table.redraw();
+        true
+    }
+
+    /// Reapply the last undone edit, also bound to Ctrl+Y.
+    /// Returns whether there was an edit to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(cells) = self.undo_stack.try_lock().unwrap().redo() else {
+            return false;
+        };
+        for (row, col, val) in cells {
+            if self.in_bounds(row, col) {
+                self.store_cell(row, col, &val);
+            }
+        }
+        self.table.redraw();
+        true
+    }
+
+    /// Returns whether there's an edit to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.try_lock().unwrap().undo.is_empty()
+    }
+
+    /// Returns whether there's an edit to redo
+    pub fn can_redo(&self) -> bool {
+        !self.undo_stack.try_lock().unwrap().redo.is_empty()
+    }
+
+    /// Forget the edit history
+    pub fn clear_undo(&mut self) {
+        self.undo_stack.try_lock().unwrap().clear();
+    }
+
+    // The edits and the find matches are kept by position, which moving, inserting or removing
+    // rows and columns invalidates
+    pub(crate) fn forget_positions(&self) {
+        self.undo_stack.try_lock().unwrap().clear();
+        self.found.try_lock().unwrap().matches.clear();
+    }
+
+    // Rows and columns may have been removed since the edit was recorded
+    fn in_bounds(&self, row: i32, col: i32) -> bool {
+        if let Some(m) = self.model.try_lock().unwrap().as_ref() {
+            return (0..m.row_count()).contains(&row) && (0..m.col_count()).contains(&col);
+        }
+        self.data
+            .try_lock()
+            .unwrap()
+            .get(row as usize)
+            .is_some_and(|r| (col as usize) < r.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(row: i32, col: i32, val: &str) -> (i32, i32, String) {
+        (row, col, val.to_string())
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut stack = UndoStack::default();
+        stack.push(0, 0, "a".into(), "b".into(), 10);
+        stack.push(1, 1, "c".into(), "d".into(), 10);
+        assert_eq!(stack.undo(), Some(vec![cell(1, 1, "c")]));
+        assert_eq!(stack.undo(), Some(vec![cell(0, 0, "a")]));
+        assert_eq!(stack.undo(), None);
+        assert_eq!(stack.redo(), Some(vec![cell(0, 0, "b")]));
+        assert_eq!(stack.undo(), Some(vec![cell(0, 0, "a")]));
+    }
+
+    #[test]
+    fn steps() {
+        let mut stack = UndoStack::default();
+        let edits = vec![cell(0, 0, "a"), cell(0, 1, "b"), cell(0, 2, "c")];
+        let edits = edits.into_iter().map(|(r, c, old)| (r, c, old, "x".into()));
+        stack.push_step(edits.collect(), 10);
+        assert_eq!(
+            stack.undo(),
+            Some(vec![cell(0, 2, "c"), cell(0, 1, "b"), cell(0, 0, "a")])
+        );
+        assert_eq!(
+            stack.redo(),
+            Some(vec![cell(0, 0, "x"), cell(0, 1, "x"), cell(0, 2, "x")])
+        );
+    }
+
+    #[test]
+    fn new_edit_clears_redo() {
+        let mut stack = UndoStack::default();
+        stack.push(0, 0, "a".into(), "b".into(), 10);
+        stack.undo();
+        stack.push(0, 0, "a".into(), "c".into(), 10);
+        assert_eq!(stack.redo(), None);
+    }
+
+    #[test]
+    fn depth() {
+        let mut stack = UndoStack::default();
+        for i in 0..5 {
+            stack.push(i, 0, "".into(), "x".into(), 3);
+        }
+        // Unchanged values aren't recorded
+        stack.push(9, 0, "x".into(), "x".into(), 3);
+        let undone: Vec<_> = std::iter::from_fn(|| stack.undo()).collect();
+        assert_eq!(undone.len(), 3);
+        assert_eq!(undone[2], vec![cell(2, 0, "")]);
+        stack.push(0, 0, "".into(), "x".into(), 0);
+        assert_eq!(stack.undo(), None);
+    }
+
+    #[test]
+    fn clear() {
+        let mut stack = UndoStack::default();
+        stack.push(0, 0, "a".into(), "b".into(), 10);
+        stack.push(0, 0, "b".into(), "c".into(), 10);
+        stack.undo();
+        stack.clear();
+        assert_eq!(stack.undo(), None);
+        assert_eq!(stack.redo(), None);
+    }
+}
@@ -0,0 +1,95 @@
+// Typed cell values
+
+use std::fmt;
+
+/// A typed cell value, see `SmartTable::set_cell_typed()`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CellValue {
+    #[default]
+    Empty,
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+}
+
+impl CellValue {
+    /// Whether the value is an integer or a float
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, CellValue::Int(_) | CellValue::Float(_))
+    }
+
+    /// The value as a float, for integers and floats
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            CellValue::Int(v) => Some(*v as f64),
+            CellValue::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    // Parses the text as a value of the same kind, None if it doesn't parse
+    pub(crate) fn parse_like(&self, txt: &str) -> Option<CellValue> {
+        let txt = txt.trim();
+        match self {
+            CellValue::Int(_) => txt.parse().ok().map(CellValue::Int),
+            CellValue::Float(_) => txt.parse().ok().map(CellValue::Float),
+            CellValue::Bool(_) => txt.parse().ok().map(CellValue::Bool),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellValue::Empty => Ok(()),
+            CellValue::Int(v) => write!(f, "{v}"),
+            CellValue::Float(v) => write!(f, "{v}"),
+            CellValue::Bool(v) => write!(f, "{v}"),
+            CellValue::Str(v) => f.write_str(v),
+        }
+    }
+}
+
+impl From<i64> for CellValue {
+    fn from(v: i64) -> Self {
+        CellValue::Int(v)
+    }
+}
+
+impl From<i32> for CellValue {
+    fn from(v: i32) -> Self {
+        CellValue::Int(v as i64)
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(v: f64) -> Self {
+        CellValue::Float(v)
+    }
+}
+
+impl From<bool> for CellValue {
+    fn from(v: bool) -> Self {
+        CellValue::Bool(v)
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(v: String) -> Self {
+        CellValue::Str(v)
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(v: &str) -> Self {
+        CellValue::Str(v.to_string())
+    }
+}
+
+impl<T: Into<CellValue>> From<Option<T>> for CellValue {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(CellValue::Empty, Into::into)
+    }
+}
@@ -0,0 +1,188 @@
+// A tabbed container of named sheets, each one a SmartTable
+
+use super::csv::{parse, quote_field};
+use super::{CsvOpts, SmartTable, TableOpts};
+use fltk::{group, prelude::*};
+use std::io::{self, Read, Write};
+
+// Height of the tab bar
+const TAB_H: i32 = 25;
+
+/// A tab bar of named SmartTables sharing the same styling, which can be saved and loaded together
+#[derive(Debug)]
+pub struct TableWorkbook {
+    tabs: group::Tabs,
+    sheets: Vec<(group::Group, SmartTable)>,
+    opts: TableOpts,
+}
+
+impl TableWorkbook {
+    /// Construct an empty workbook using coords and size, new sheets are styled with `opts`
+    pub fn new(x: i32, y: i32, w: i32, h: i32, opts: TableOpts) -> Self {
+        let tabs = group::Tabs::new(x, y, w, h, None);
+        tabs.end();
+        Self {
+            tabs,
+            sheets: vec![],
+            opts,
+        }
+    }
+
+    /// Add a sheet with the given number of rows and columns, returning its table
+    pub fn add_sheet(&mut self, name: &str, rows: i32, cols: i32) -> SmartTable {
+        let (x, y, w, h) = (
+            self.tabs.x(),
+            self.tabs.y() + TAB_H,
+            self.tabs.w(),
+            self.tabs.h() - TAB_H,
+        );
+        self.tabs.begin();
+        let mut grp = group::Group::new(x, y, w, h, None);
+        grp.set_label(name);
+        let mut table = SmartTable::new(x, y, w, h, None);
+        table.set_opts(TableOpts {
+            rows,
+            cols,
+            ..self.opts
+        });
+        grp.end();
+        grp.resizable(&*table);
+        self.tabs.end();
+        self.tabs.resizable(&grp);
+        self.tabs.set_value(&grp).ok();
+        self.tabs.redraw();
+        self.sheets.push((grp, table.clone()));
+        table
+    }
+
+    /// Rename the sheet at the index
+    pub fn rename_sheet(&mut self, idx: usize, name: &str) {
+        self.sheets[idx].0.set_label(name);
+        self.tabs.redraw();
+    }
+
+    /// Remove the sheet at the index
+    pub fn remove_sheet(&mut self, idx: usize) {
+        let (grp, _) = self.sheets.remove(idx);
+        self.tabs.remove(&grp);
+        group::Group::delete(grp);
+        if let Some((grp, _)) = self
+            .sheets
+            .get(idx.min(self.sheets.len().saturating_sub(1)))
+        {
+            self.tabs.set_value(grp).ok();
+        }
+        self.tabs.redraw();
+    }
+
+    /// Get the sheet at the index
+    pub fn sheet(&self, idx: usize) -> Option<SmartTable> {
+        self.sheets.get(idx).map(|(_, t)| t.clone())
+    }
+
+    /// Get the first sheet with the given name
+    pub fn sheet_by_name(&self, name: &str) -> Option<SmartTable> {
+        self.sheets
+            .iter()
+            .find(|(g, _)| g.label() == name)
+            .map(|(_, t)| t.clone())
+    }
+
+    /// Returns the names of the sheets, in tab order
+    pub fn sheet_names(&self) -> Vec<String> {
+        self.sheets.iter().map(|(g, _)| g.label()).collect()
+    }
+
+    /// Returns the number of sheets
+    pub fn sheet_count(&self) -> usize {
+        self.sheets.len()
+    }
+
+    /// Returns the index of the shown sheet
+    pub fn current_sheet(&self) -> Option<usize> {
+        let current = self.tabs.value()?;
+        self.sheets
+            .iter()
+            .position(|(g, _)| g.as_widget_ptr() == current.as_widget_ptr())
+    }
+
+    /// Show the sheet at the index
+    pub fn set_current_sheet(&mut self, idx: usize) {
+        if let Some((grp, _)) = self.sheets.get(idx) {
+            self.tabs.set_value(grp).ok();
+        }
+    }
+
+    /// Restyle every sheet, keeping their contents. Only the styling fields of `opts` are used,
+    /// each sheet keeps its dimensions
+    pub fn set_opts(&mut self, opts: TableOpts) {
+        self.opts = opts;
+        for (_, table) in &mut self.sheets {
+            let checkpoint = table.checkpoint();
+            table.set_opts(TableOpts {
+                rows: table.row_count(),
+                cols: table.column_count(),
+                ..opts
+            });
+            table.rollback(checkpoint);
+        }
+    }
+
+    /// Write every sheet as CSV into one stream. Each record starts with the name of its sheet,
+    /// and the first record of every sheet holds its column headers
+    pub fn save<W: Write>(&self, mut writer: W, opts: &CsvOpts) -> io::Result<()> {
+        let delim = opts.delimiter.to_string();
+        for (grp, table) in &self.sheets {
+            let name = quote_field(&grp.label(), opts);
+            let headers = table.col_headers.try_lock().unwrap().clone();
+            let rows = table
+                .data
+                .try_lock()
+                .unwrap()
+                .iter()
+                .map(|r| r.iter().map(|c| c.label.clone()).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            for record in std::iter::once(headers).chain(rows) {
+                let mut line = name.clone();
+                for field in record {
+                    line.push_str(&delim);
+                    line.push_str(&quote_field(&field, opts));
+                }
+                line.push_str("\r\n");
+                writer.write_all(&opts.encoding.encode(&line))?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Replace all the sheets with the ones read from a stream written by `save()`
+    pub fn load<R: Read>(&mut self, mut reader: R, opts: &CsvOpts) -> io::Result<()> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        let records = parse(&opts.encoding.decode(&bytes), opts);
+        while !self.sheets.is_empty() {
+            self.remove_sheet(0);
+        }
+        let mut start = 0;
+        while start < records.len() {
+            let name = records[start][0].clone();
+            let end = records[start..]
+                .iter()
+                .position(|r| r[0] != name)
+                .map_or(records.len(), |n| start + n);
+            let sheet: Vec<Vec<String>> = records[start..end]
+                .iter()
+                .map(|r| r[1..].to_vec())
+                .collect();
+            let mut table = self.add_sheet(&name, 0, 0);
+            table.load_records(sheet, true);
+            start = end;
+        }
+        if let Some((grp, _)) = self.sheets.first() {
+            self.tabs.set_value(grp).ok();
+        }
+        Ok(())
+    }
+}
+
+fltk::widget_extends!(TableWorkbook, group::Tabs, tabs);
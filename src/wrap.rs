@@ -0,0 +1,93 @@
+// Word wrapped columns, growing the rows to fit their text
+
+use super::{filter, CellMatrix, ColProps, SmartTable, TableModel, TableOpts};
+use fltk::{
+    draw,
+    prelude::{TableExt, WidgetExt},
+    table,
+};
+use std::collections::HashMap;
+
+// The height a row had before it was grown and the height it was given, by row
+pub(crate) type GrownRows = HashMap<i32, (i32, i32)>;
+
+impl SmartTable {
+    /// Wrap the text of the column's cells onto multiple lines,
+    /// the visible rows grow to fit the wrapped text
+    pub fn set_col_wrap(&mut self, col: i32, wrap: bool) {
+        self.col_props.try_lock().unwrap()[col as usize].wrap = wrap;
+        self.table.redraw();
+    }
+
+    /// Returns whether the column's text is wrapped
+    pub fn col_wrap(&self, col: i32) -> bool {
+        self.col_props
+            .try_lock()
+            .unwrap()
+            .get(col as usize)
+            .is_some_and(|p| p.wrap)
+    }
+
+    // Sets the heights of the visible rows to fit the text of their wrapped cells. A row
+    // shrinks back to its previous height when the text gets shorter, unless resized since
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn fit_wrapped_rows(
+        t: &mut table::TableRow,
+        data: &CellMatrix,
+        model: Option<&dyn TableModel>,
+        col_props: &[ColProps],
+        filter: &Option<filter::RowFilter>,
+        opts: &TableOpts,
+        grown: &mut GrownRows,
+    ) {
+        if !col_props.iter().any(|p| p.wrap) {
+            return;
+        }
+        let ncols = col_props.len() as i32;
+        let margin = 2 * (opts.cell_padding + opts.cell_border_width);
+        let (r1, r2, _, _) = t.visible_cells();
+        for view_row in r1.max(0)..=r2.min(t.rows() - 1) {
+            let row = Self::data_row(filter, view_row);
+            let mut need = 0;
+            for (col, _) in col_props
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.wrap && p.hidden.is_none())
+            {
+                let w = t.col_width(Self::display_col(opts.rtl, ncols, col as i32)) - margin;
+                let (txt, font, size) = match model {
+                    Some(m) => (m.cell(row, col as i32), opts.cell_font, opts.cell_font_size),
+                    None => {
+                        let Some(c) = data.get(row as usize).and_then(|r| r.get(col)) else {
+                            continue;
+                        };
+                        (
+                            c.label.clone(),
+                            c.font.unwrap_or(opts.cell_font),
+                            c.font_size.unwrap_or(opts.cell_font_size),
+                        )
+                    }
+                };
+                if txt.is_empty() || w <= 0 {
+                    continue;
+                }
+                draw::set_font(font, size);
+                need = need.max(draw::wrap_measure(&txt, w, false).1 + margin);
+            }
+            let cur = t.row_height(view_row);
+            let base = match grown.get(&view_row) {
+                Some((base, given)) if *given == cur => *base,
+                _ => cur,
+            };
+            let h = need.max(base);
+            if h != cur {
+                t.set_row_height(view_row, h);
+            }
+            if h == base {
+                grown.remove(&view_row);
+            } else {
+                grown.insert(view_row, (base, h));
+            }
+        }
+    }
+}
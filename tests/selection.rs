@@ -0,0 +1,53 @@
+use fltk::app;
+use fltk_table::{SelectionMode, SmartTable, TableOpts};
+
+fn table(mode: SelectionMode) -> SmartTable {
+    SmartTable::default()
+        .with_size(400, 300)
+        .with_opts(TableOpts {
+            rows: 4,
+            cols: 3,
+            editable: true,
+            selection_mode: mode,
+            ..Default::default()
+        })
+}
+
+fn selected_cells() {
+    let mut t = table(SelectionMode::Cell);
+    t.select_range(1, 1, 2, 2);
+    assert_eq!(t.selected_cells(), [(1, 1), (1, 2), (2, 1), (2, 2)]);
+    for mode in [SelectionMode::Row, SelectionMode::MultiRow] {
+        let mut t = table(mode);
+        t.select_range(2, 0, 2, 0);
+        assert_eq!(t.selected_cells(), [(2, 0), (2, 1), (2, 2)]);
+    }
+}
+
+fn clear_selection_contents() {
+    let mut t = table(SelectionMode::Cell);
+    for row in 0..4 {
+        for col in 0..3 {
+            t.set_cell_value(row, col, &format!("{row}{col}"));
+        }
+    }
+    t.lock_cell(1, 0);
+    t.set_col_editable(2, false);
+    t.select_range(1, 0, 2, 2);
+    assert!(t.clear_selection_contents());
+    assert_eq!(t.cell_value(1, 0), "10");
+    assert_eq!(t.cell_value(1, 1), "");
+    assert_eq!(t.cell_value(1, 2), "12");
+    assert_eq!(t.cell_value(2, 0), "");
+    assert_eq!(t.cell_value(2, 2), "22");
+    assert_eq!(t.cell_value(0, 0), "00");
+    assert_eq!(t.cell_value(3, 1), "31");
+    t.undo();
+    assert_eq!(t.cell_value(2, 0), "20");
+}
+
+fn main() {
+    let _app = app::App::default();
+    selected_cells();
+    clear_selection_contents();
+}